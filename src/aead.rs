@@ -0,0 +1,236 @@
+//! Authenticated encryption with associated data.
+//!
+//! A [`SymmetricKey`] seals messages into [`Envelope`]s, which carry the
+//! cipher and nonce along with the ciphertext and can be serialized with
+//! serde. Nonces are either drawn at random, which is the default with the
+//! 192-bit nonces of XChaCha20-Poly1305, or taken from a counter by a
+//! [`Sealer`] and checked in order by an [`Opener`], e.g. for the session keys
+//! of a channel.
+
+use std::fmt;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::{CryptoRng, RngCore};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::Error;
+
+/// An AEAD cipher with 256-bit keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Cipher {
+    /// XChaCha20-Poly1305, with 192-bit nonces that are safe to draw at random.
+    XChaCha20Poly1305,
+    /// AES-256-GCM, as implemented by ring. Its 96-bit nonces should only be
+    /// drawn at random for up to 2^32 messages per key.
+    Aes256Gcm,
+}
+
+impl Cipher {
+    /// The length of nonces in bytes.
+    pub fn nonce_len(&self) -> usize {
+        match self {
+            Cipher::XChaCha20Poly1305 => 24,
+            Cipher::Aes256Gcm => 12,
+        }
+    }
+
+    /// The nonce of message number `counter`, in big-endian at the end of the nonce.
+    fn counter_nonce(&self, counter: u64) -> Vec<u8> {
+        let mut nonce = vec![0; self.nonce_len()];
+        let len = nonce.len();
+        nonce[len - 8..].copy_from_slice(&counter.to_be_bytes());
+        nonce
+    }
+}
+
+/// A sealed message, with the cipher and nonce needed to open it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope {
+    pub cipher: Cipher,
+    pub nonce: Vec<u8>,
+    /// The encrypted message followed by the authentication tag.
+    pub ciphertext: Vec<u8>,
+}
+
+/// A 256-bit symmetric key.
+///
+/// The key is wiped from memory when dropped.
+///
+/// Keys are compared in constant time, and are deliberately neither `Hash` nor
+/// `Ord`.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct SymmetricKey([u8; 32]);
+
+impl ConstantTimeEq for SymmetricKey {
+    fn ct_eq(&self, other: &Self) -> Choice { self.0.ct_eq(&other.0) }
+}
+
+impl PartialEq for SymmetricKey {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
+}
+
+impl Eq for SymmetricKey {}
+
+impl fmt::Debug for SymmetricKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SymmetricKey") }
+}
+
+/// Create a key from its 32 bytes, e.g. one of the session keys of a key
+/// exchange.
+impl From<[u8; 32]> for SymmetricKey {
+    fn from(bytes: [u8; 32]) -> SymmetricKey { SymmetricKey(bytes) }
+}
+
+impl SymmetricKey {
+    /// Generate a new random key.
+    pub fn generate() -> SymmetricKey { Self::generate_with_rng(&mut rand::thread_rng()) }
+
+    /// Generate a new key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SymmetricKey {
+        let mut key = SymmetricKey([0; 32]);
+        rng.fill_bytes(&mut key.0);
+        key
+    }
+
+    /// The bytes of the key.
+    pub fn as_bytes(&self) -> &[u8; 32] { &self.0 }
+
+    /// Seal `msg` and authenticate it along with `aad` using
+    /// XChaCha20-Poly1305 and a random nonce.
+    pub fn seal(&self, msg: &[u8], aad: &[u8]) -> Envelope {
+        self.seal_with(Cipher::XChaCha20Poly1305, msg, aad)
+    }
+
+    /// Seal `msg` and authenticate it along with `aad` using `cipher` and a
+    /// random nonce.
+    pub fn seal_with(&self, cipher: Cipher, msg: &[u8], aad: &[u8]) -> Envelope {
+        let mut nonce = vec![0; cipher.nonce_len()];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        self.seal_with_nonce(cipher, nonce, msg, aad)
+    }
+
+    fn seal_with_nonce(&self, cipher: Cipher, nonce: Vec<u8>, msg: &[u8], aad: &[u8]) -> Envelope {
+        let ciphertext = match cipher {
+            Cipher::XChaCha20Poly1305 => XChaCha20Poly1305::new(&self.0.into())
+                .encrypt(XNonce::from_slice(&nonce), Payload { msg, aad })
+                .expect("XChaCha20-Poly1305 encryption failed."),
+            Cipher::Aes256Gcm => {
+                let nonce = Nonce::try_assume_unique_for_key(&nonce).expect("12-byte nonce");
+                let mut in_out = msg.to_vec();
+                self.aes_256_gcm()
+                    .seal_in_place_append_tag(nonce, Aad::from(aad), &mut in_out)
+                    .expect("AES-256-GCM encryption failed.");
+                in_out
+            }
+        };
+        Envelope { cipher, nonce, ciphertext }
+    }
+
+    /// Open an envelope sealed with this key and `aad`.
+    ///
+    /// Fails if the envelope was sealed with another key or other associated
+    /// data, or has been tampered with.
+    pub fn open(&self, envelope: &Envelope, aad: &[u8]) -> Result<Vec<u8>, Error> {
+        let Envelope { cipher, nonce, ciphertext } = envelope;
+        if nonce.len() != cipher.nonce_len() {
+            return Err(Error::Decryption("invalid nonce length"));
+        }
+        let plaintext = match cipher {
+            Cipher::XChaCha20Poly1305 => XChaCha20Poly1305::new(&self.0.into())
+                .decrypt(XNonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+                .ok(),
+            Cipher::Aes256Gcm => {
+                let nonce = Nonce::try_assume_unique_for_key(nonce).expect("12-byte nonce");
+                let mut in_out = ciphertext.clone();
+                let len = self
+                    .aes_256_gcm()
+                    .open_in_place(nonce, Aad::from(aad), &mut in_out)
+                    .map(|plaintext| plaintext.len())
+                    .ok();
+                len.map(|len| {
+                    in_out.truncate(len);
+                    in_out
+                })
+            }
+        };
+        plaintext.ok_or(Error::Decryption("authentication failed"))
+    }
+
+    fn aes_256_gcm(&self) -> LessSafeKey {
+        LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &self.0).expect("32-byte key"))
+    }
+}
+
+/// Seals a sequence of messages under one key, taking the nonces from a
+/// counter so that they never repeat.
+///
+/// Each key must only ever be used by a single sealer, e.g. one of the two
+/// directional session keys of a channel.
+pub struct Sealer {
+    key: SymmetricKey,
+    cipher: Cipher,
+    counter: u64,
+}
+
+impl fmt::Debug for Sealer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sealer")
+            .field("cipher", &self.cipher)
+            .field("counter", &self.counter)
+            .finish()
+    }
+}
+
+impl Sealer {
+    /// Create a sealer whose first message has nonce 0.
+    pub fn new(key: SymmetricKey, cipher: Cipher) -> Sealer { Sealer { key, cipher, counter: 0 } }
+
+    /// Seal the next message, see [`SymmetricKey::seal`].
+    ///
+    /// Panics once the counter is exhausted, after 2^64 - 1 messages.
+    pub fn seal(&mut self, msg: &[u8], aad: &[u8]) -> Envelope {
+        let counter = self.counter;
+        self.counter = counter.checked_add(1).expect("The nonce counter is exhausted");
+        self.key.seal_with_nonce(self.cipher, self.cipher.counter_nonce(counter), msg, aad)
+    }
+}
+
+/// Opens the messages of a [`Sealer`] in order, rejecting messages that are
+/// replayed, reordered or dropped.
+pub struct Opener {
+    key: SymmetricKey,
+    cipher: Cipher,
+    counter: u64,
+}
+
+impl fmt::Debug for Opener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Opener")
+            .field("cipher", &self.cipher)
+            .field("counter", &self.counter)
+            .finish()
+    }
+}
+
+impl Opener {
+    /// Create an opener expecting the first message of a sealer.
+    pub fn new(key: SymmetricKey, cipher: Cipher) -> Opener { Opener { key, cipher, counter: 0 } }
+
+    /// Open the next message, see [`SymmetricKey::open`].
+    ///
+    /// Fails if the envelope is not the next message of the sealer, in which
+    /// case the opener still expects the same message.
+    pub fn open(&mut self, envelope: &Envelope, aad: &[u8]) -> Result<Vec<u8>, Error> {
+        let expected = self.cipher.counter_nonce(self.counter);
+        if envelope.cipher != self.cipher || envelope.nonce != expected {
+            return Err(Error::Decryption("unexpected nonce"));
+        }
+        let plaintext = self.key.open(envelope, aad)?;
+        self.counter += 1;
+        Ok(plaintext)
+    }
+}
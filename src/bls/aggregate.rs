@@ -0,0 +1,49 @@
+use blstrs::{G1Projective, G2Projective};
+use group::Group;
+
+use super::public::{core_verify, decode_signature};
+use super::{PublicKey, DST_SIG};
+use crate::{Algorithm, Error, SigningError, VerificationError};
+
+/// Aggregate BLS signatures into a single signature of the same size.
+///
+/// Fails if any of the signatures is not a valid compressed G2 point.
+pub fn aggregate_signatures(signatures: &[&[u8]]) -> Result<Vec<u8>, Error> {
+    if signatures.is_empty() {
+        let msg = "cannot aggregate an empty set of signatures";
+        return Err(SigningError::new(Algorithm::BLS, msg).into());
+    }
+    let mut agg = G2Projective::identity();
+    for sig in signatures {
+        let sig = decode_signature(sig).ok_or(VerificationError::Malformed("BLS signature"))?;
+        agg += sig;
+    }
+    Ok(agg.to_compressed().to_vec())
+}
+
+impl PublicKey {
+    /// Aggregate public keys into a single public key, which verifies aggregate
+    /// signatures on a common message.
+    ///
+    /// Every key must come with a verified proof of possession, otherwise a rogue
+    /// key can be crafted to forge aggregate signatures.
+    pub fn aggregate(public_keys: &[&PublicKey]) -> Option<PublicKey> {
+        if public_keys.is_empty() {
+            return None;
+        }
+        let agg = public_keys.iter().fold(G1Projective::identity(), |acc, pk| acc + pk.0);
+        Some(PublicKey(agg.into()))
+    }
+
+    /// Verify an aggregate signature produced by `aggregate_signatures` on a single
+    /// message signed by all `public_keys`.
+    ///
+    /// Every key must come with a verified proof of possession, see
+    /// `PublicKey::verify_possession`.
+    pub fn verify_aggregate(msg: &[u8], sig: &[u8], public_keys: &[&PublicKey]) -> bool {
+        match (PublicKey::aggregate(public_keys), decode_signature(sig)) {
+            (Some(pk), Some(sig)) => core_verify(&pk.0, msg, DST_SIG, &sig),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::ZeroizeOnDrop;
+
+use super::{PublicKey, SecretKey};
+
+/// A BLS12-381 keypair.
+///
+/// Keypairs are hashed and ordered by their public key.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Keypair {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+impl Keypair {
+    pub const PK_SIZE: usize = 48;
+    pub const PVT_SIZE: usize = 32;
+
+    /// Generate a new BLS12-381 `Keypair`.
+    pub fn generate() -> Keypair { Keypair::from(SecretKey::generate()) }
+
+    /// Generate a new keypair from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Keypair {
+        Keypair::from(SecretKey::generate_with_rng(rng))
+    }
+
+    /// Deterministically derive a keypair from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> Keypair { Keypair::from(SecretKey::from_seed(seed)) }
+
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> &PublicKey { &self.public }
+
+    /// Get the secret key of this keypair.
+    pub fn secret(&self) -> &SecretKey { &self.secret }
+
+    /// Produce a proof of possession of the secret key, to be published along
+    /// with the public key.
+    pub fn prove_possession(&self) -> Vec<u8> { self.secret.prove_possession() }
+}
+
+impl ZeroizeOnDrop for Keypair {}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair").field("public", &self.public).finish()
+    }
+}
+
+/// Promote a BLS12-381 secret key into a keypair.
+impl From<SecretKey> for Keypair {
+    fn from(secret: SecretKey) -> Keypair {
+        let public = secret.public();
+        Keypair { secret, public }
+    }
+}
+
+/// Demote a BLS12-381 keypair into a secret key.
+impl From<Keypair> for SecretKey {
+    fn from(kp: Keypair) -> SecretKey { kp.secret }
+}
+
+impl std::hash::Hash for Keypair {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.public.hash(state); }
+}
+
+impl PartialOrd for Keypair {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Keypair {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.public.cmp(&other.public) }
+}
@@ -0,0 +1,36 @@
+//! BLS12-381 keys.
+//!
+//! Implements the minimal-pubkey-size variant of the [BLS signature scheme] with the
+//! proof-of-possession ciphersuite: public keys live in G1 (48 bytes compressed) and
+//! signatures in G2 (96 bytes compressed). Signatures on the same message can be
+//! aggregated into a single signature that verifies against the set of signers.
+//!
+//! Aggregate verification is only secure against rogue-key attacks if every public key
+//! taking part has been registered together with a valid proof of possession, see
+//! [`SecretKey::prove_possession`] and [`PublicKey::verify_possession`].
+//!
+//! [BLS signature scheme]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05
+
+mod keypair;
+pub use keypair::*;
+
+mod public;
+pub use public::*;
+
+mod secret;
+pub use secret::*;
+
+mod aggregate;
+pub use aggregate::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Domain separation tag used when hashing messages to G2.
+pub(crate) const DST_SIG: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain separation tag used when hashing public keys to G2 for proofs of possession.
+pub(crate) const DST_POP: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Size of a compressed signature.
+pub const SIGNATURE_SIZE: usize = 96;
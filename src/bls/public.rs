@@ -0,0 +1,117 @@
+use std::{fmt, hash::Hash};
+
+use blstrs::{Bls12, G1Affine, G2Affine, G2Prepared, G2Projective};
+use group::prime::PrimeCurveAffine;
+use group::Group;
+use pairing::{MillerLoopResult, MultiMillerLoop};
+use serde::{Deserialize, Serialize};
+
+use super::{DST_POP, DST_SIG, SIGNATURE_SIZE};
+use crate::{Algorithm, DecodingError, Error, VerificationError};
+
+/// A BLS12-381 public key, i.e. a point in G1.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct PublicKey(pub(crate) G1Affine);
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PublicKey(compressed): ")?;
+        for byte in &self.encode() {
+            write!(f, "{:x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derived_hash_with_manual_eq)]
+impl Hash for PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.encode().hash(state); }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.encode().cmp(&other.encode()) }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.encode())
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        PublicKey::decode(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse a compressed G2 point, checking that it is in the prime-order subgroup.
+pub(crate) fn decode_signature(sig: &[u8]) -> Option<G2Affine> {
+    let bytes: &[u8; SIGNATURE_SIZE] = sig.try_into().ok()?;
+    G2Affine::from_compressed(bytes).into()
+}
+
+/// Check `e(pk, H(msg)) == e(g1, sig)` where `H` hashes to G2 under `dst`.
+pub(crate) fn core_verify(pk: &G1Affine, msg: &[u8], dst: &[u8], sig: &G2Affine) -> bool {
+    let h: G2Affine = G2Projective::hash_to_curve(msg, dst, &[]).into();
+    let g1 = -G1Affine::generator();
+    Bls12::multi_miller_loop(&[(pk, &G2Prepared::from(h)), (&g1, &G2Prepared::from(*sig))])
+        .final_exponentiation()
+        .is_identity()
+        .into()
+}
+
+impl PublicKey {
+    /// Verify the BLS signature on a message using the public key.
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool { self.try_verify(msg, sig).is_ok() }
+
+    /// Verify the BLS signature on a message like `verify`, but report why it is invalid.
+    pub fn try_verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
+        if sig.len() != SIGNATURE_SIZE {
+            return Err(VerificationError::InvalidLength {
+                algorithm: "BLS",
+                expected: SIGNATURE_SIZE,
+                actual: sig.len(),
+            });
+        }
+        let sig = decode_signature(sig).ok_or(VerificationError::Malformed("BLS signature"))?;
+        if !core_verify(&self.0, msg, DST_SIG, &sig) {
+            return Err(VerificationError::Invalid("BLS"));
+        }
+        Ok(())
+    }
+
+    /// Verify a proof of possession produced by `SecretKey::prove_possession`.
+    pub fn verify_possession(&self, proof: &[u8]) -> bool {
+        match decode_signature(proof) {
+            Some(proof) => core_verify(&self.0, &self.encode(), DST_POP, &proof),
+            None => false,
+        }
+    }
+
+    /// Encode the public key as a compressed G1 point.
+    pub fn encode(&self) -> [u8; 48] { self.0.to_compressed() }
+
+    /// Decode a public key from a byte slice in the format produced by `encode`.
+    /// Points outside the prime-order subgroup and the identity are rejected.
+    pub fn decode(k: &[u8]) -> Result<PublicKey, Error> {
+        let error = |msg| DecodingError::new(Algorithm::BLS, msg);
+        let bytes: &[u8; 48] = k.try_into().map_err(|_| error("public key must be 48 bytes"))?;
+        let pk = Option::<G1Affine>::from(G1Affine::from_compressed(bytes))
+            .ok_or_else(|| error("public key is not a compressed G1 point"))?;
+        if bool::from(pk.is_identity()) {
+            return Err(error("public key is the identity").into());
+        }
+        Ok(PublicKey(pk))
+    }
+}
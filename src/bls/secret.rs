@@ -0,0 +1,128 @@
+use std::fmt;
+
+use blstrs::{G1Projective, G2Projective, Scalar};
+use ff::Field;
+use group::Group;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::{PublicKey, DST_POP, DST_SIG};
+use crate::{Algorithm, DecodingError, Error};
+
+/// A BLS12-381 secret key.
+///
+/// The key is wiped from memory when dropped.
+///
+/// Secret keys are compared in constant time, and are deliberately neither
+/// `Hash` nor `Ord`.
+#[derive(Clone)]
+pub struct SecretKey(pub(crate) Scalar);
+
+/// Overwrite a scalar with zero in a way the compiler cannot optimize away.
+pub(crate) fn clear_scalar(s: &mut Scalar) {
+    // SAFETY: `s` is a valid, aligned and exclusive reference.
+    unsafe { std::ptr::write_volatile(s, Scalar::ZERO) };
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) { clear_scalar(&mut self.0); }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) { self.zeroize(); }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SecretKey") }
+}
+
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice { self.0.ct_eq(&other.0) }
+}
+
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
+}
+
+impl Eq for SecretKey {}
+
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+struct Serialized([u8; 32]);
+
+impl Serialize for SecretKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Serialized(self.to_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = Serialized::deserialize(deserializer)?;
+        SecretKey::from_bytes(data.0).map_err(serde::de::Error::custom)
+    }
+}
+
+impl SecretKey {
+    /// Generate a new BLS12-381 secret key.
+    pub fn generate() -> SecretKey { Self::generate_with_rng(&mut rand::thread_rng()) }
+
+    /// Generate a new BLS12-381 secret key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SecretKey {
+        loop {
+            let s = Scalar::random(&mut *rng);
+            if !bool::from(s.is_zero()) {
+                return SecretKey(s);
+            }
+        }
+    }
+
+    /// Deterministically derive a secret key from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> SecretKey {
+        Self::generate_with_rng(&mut crate::seeded_rng(seed))
+    }
+
+    /// Create a secret key from its big-endian byte encoding. Fails if the bytes are
+    /// not a canonical, non-zero scalar.
+    pub fn from_bytes(sk: impl AsRef<[u8]>) -> Result<SecretKey, Error> {
+        let error = |msg| DecodingError::new(Algorithm::BLS, msg);
+        let bytes: [u8; 32] =
+            sk.as_ref().try_into().map_err(|_| error("secret key must be 32 bytes"))?;
+        let s = Option::<Scalar>::from(Scalar::from_bytes_be(&bytes))
+            .ok_or_else(|| error("secret key is not a canonical scalar"))?;
+        if bool::from(s.is_zero()) {
+            return Err(error("secret key is zero").into());
+        }
+        Ok(SecretKey(s))
+    }
+
+    /// Returns the big-endian bytes of the secret key.
+    pub fn to_bytes(&self) -> [u8; 32] { self.0.to_bytes_be() }
+
+    /// Get the public key corresponding to this secret key.
+    pub fn public(&self) -> PublicKey { PublicKey((G1Projective::generator() * self.0).into()) }
+
+    /// Sign a message with this secret key, producing a compressed G2 point.
+    pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        let h = G2Projective::hash_to_curve(msg, DST_SIG, &[]);
+        Ok((h * self.0).to_compressed().to_vec())
+    }
+
+    /// Produce a proof of possession of this secret key, i.e. a signature on the
+    /// encoded public key under a separate domain.
+    pub fn prove_possession(&self) -> Vec<u8> {
+        let h = G2Projective::hash_to_curve(&self.public().encode(), DST_POP, &[]);
+        (h * self.0).to_compressed().to_vec()
+    }
+}
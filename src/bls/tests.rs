@@ -0,0 +1,80 @@
+use zeroize::Zeroize;
+
+use super::*;
+use crate::tests::assert_zeroize_on_drop;
+
+#[test]
+fn bls_sign_verify() {
+    let kp = Keypair::generate();
+    let msg = "hello world".as_bytes();
+    let sig = kp.secret().sign(msg).unwrap();
+    assert_eq!(sig.len(), SIGNATURE_SIZE);
+    assert!(kp.public().verify(msg, &sig));
+    assert!(!kp.public().verify("h3ll0 w0rld".as_bytes(), &sig));
+    assert!(!Keypair::generate().public().verify(msg, &sig));
+    assert!(!kp.public().verify(msg, &sig[1..]));
+}
+
+#[test]
+fn bls_codec() {
+    let kp = Keypair::generate();
+    let pk = PublicKey::decode(&kp.public().encode()).unwrap();
+    assert_eq!(&pk, kp.public());
+    let sk = SecretKey::from_bytes(kp.secret().to_bytes()).unwrap();
+    assert_eq!(&sk, kp.secret());
+
+    let decoded: Keypair = bincode::deserialize(&bincode::serialize(&kp).unwrap()).unwrap();
+    assert_eq!(decoded, kp);
+    assert!(SecretKey::from_bytes([0u8; 32]).is_err());
+}
+
+#[test]
+fn bls_proof_of_possession() {
+    let kp = Keypair::generate();
+    let pop = kp.prove_possession();
+    assert!(kp.public().verify_possession(&pop));
+    assert!(!Keypair::generate().public().verify_possession(&pop));
+
+    // A signature on the encoded public key is not a proof of possession.
+    let sig = kp.secret().sign(&kp.public().encode()).unwrap();
+    assert!(!kp.public().verify_possession(&sig));
+}
+
+#[test]
+fn bls_aggregate() {
+    let msg = "block 42".as_bytes();
+    let kps: Vec<Keypair> = (0..5).map(|_| Keypair::generate()).collect();
+    let sigs: Vec<Vec<u8>> = kps.iter().map(|kp| kp.secret().sign(msg).unwrap()).collect();
+    let sig_refs: Vec<&[u8]> = sigs.iter().map(|s| s.as_slice()).collect();
+    let agg = aggregate_signatures(&sig_refs).unwrap();
+    assert_eq!(agg.len(), SIGNATURE_SIZE);
+
+    let pks: Vec<&PublicKey> = kps.iter().map(|kp| kp.public()).collect();
+    assert!(PublicKey::verify_aggregate(msg, &agg, &pks));
+    assert!(!PublicKey::verify_aggregate("block 43".as_bytes(), &agg, &pks));
+    assert!(!PublicKey::verify_aggregate(msg, &agg, &pks[1..]));
+    assert!(!PublicKey::verify_aggregate(msg, &agg, &[]));
+    assert!(aggregate_signatures(&[]).is_err());
+}
+
+#[test]
+fn bls_generic_keypair() {
+    let kp = crate::Keypair::generate_bls();
+    let msg = "hello world".as_bytes();
+    let sig = kp.private().sign(msg).unwrap();
+    assert!(kp.public().verify(msg, &sig));
+
+    let decoded: crate::PublicKey =
+        bincode::deserialize(&bincode::serialize(&kp.public()).unwrap()).unwrap();
+    assert_eq!(decoded, kp.public());
+}
+
+#[test]
+fn bls_secret_is_wiped() {
+    let mut sk = Keypair::generate().secret().clone();
+    sk.zeroize();
+    assert_eq!(sk.to_bytes(), [0; 32]);
+
+    assert_zeroize_on_drop::<SecretKey>();
+    assert_zeroize_on_drop::<Keypair>();
+}
@@ -1,22 +1,42 @@
+use std::fmt;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+/// A signature scheme supported by this crate.
+///
+/// Algorithms display as their canonical name, and parse from it or one of its
+/// common aliases, ignoring case, dashes and underscores: `"ed25519"`,
+/// `"secp256k1"` or `"k256"`, `"p256"`, `"secp256r1"` or `"prime256v1"`,
+/// `"bls"` or `"bls12-381"`, and `"rsa"`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Algorithm {
     RSA,
     ED25519,
     SECP256K1,
+    BLS,
+    P256,
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(self, f) }
 }
 
 impl FromStr for Algorithm {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+        let name: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_'))
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        match name.as_str() {
             "RSA" => Ok(Algorithm::RSA),
             "ED25519" => Ok(Algorithm::ED25519),
-            "SECP256K1" => Ok(Algorithm::SECP256K1),
+            "SECP256K1" | "K256" => Ok(Algorithm::SECP256K1),
+            "BLS" | "BLS12381" => Ok(Algorithm::BLS),
+            "P256" | "SECP256R1" | "PRIME256V1" => Ok(Algorithm::P256),
             _ => Err("no match"),
         }
     }
@@ -0,0 +1,121 @@
+//! Hierarchical deterministic key derivation.
+//!
+//! Keys are derived from a master seed along a [`DerivationPath`], following
+//! [SLIP-10] for Ed25519 and [BIP-32] for Secp256k1, so that a single backed
+//! up seed recovers every key of a node. Ed25519 only supports hardened
+//! derivation.
+//!
+//! [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+//! [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(any(feature = "ed25519", feature = "secp256k1"))]
+use ring::hmac;
+#[cfg(any(feature = "ed25519", feature = "secp256k1"))]
+use zeroize::Zeroizing;
+
+/// A derivation path such as `m/44'/0'/1'`.
+///
+/// Paths parse from and display as `m` followed by `/`-separated child
+/// indices, where hardened indices are marked with a trailing `'` (or `h`
+/// when parsing).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The offset of hardened child indices.
+    pub const HARDENED: u32 = 1 << 31;
+
+    /// The child indices of the path, with `HARDENED` added to hardened ones.
+    pub fn indices(&self) -> &[u32] { &self.0 }
+
+    /// Whether `index` is a hardened child index.
+    pub fn is_hardened(index: u32) -> bool { index >= Self::HARDENED }
+
+    /// Extend the path with the child `index`.
+    pub fn child(&self, index: u32) -> DerivationPath {
+        let mut indices = self.0.clone();
+        indices.push(index);
+        DerivationPath(indices)
+    }
+}
+
+impl From<Vec<u32>> for DerivationPath {
+    fn from(indices: Vec<u32>) -> Self { DerivationPath(indices) }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for &index in &self.0 {
+            if Self::is_hardened(index) {
+                write!(f, "/{}'", index - Self::HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err("derivation path must start with m");
+        }
+        parts
+            .map(|part| {
+                let (digits, offset) = match part.strip_suffix(['\'', 'h', 'H']) {
+                    Some(digits) => (digits, Self::HARDENED),
+                    None => (part, 0),
+                };
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err("invalid child index");
+                }
+                match digits.parse::<u32>() {
+                    Ok(index) if index < Self::HARDENED => Ok(index + offset),
+                    _ => Err("child index out of range"),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(DerivationPath)
+    }
+}
+
+/// A secret key and chain code, split from the output of HMAC-SHA512.
+#[cfg(any(feature = "ed25519", feature = "secp256k1"))]
+pub(crate) struct ExtendedKey {
+    pub(crate) key: Zeroizing<[u8; 32]>,
+    chain_code: Zeroizing<[u8; 32]>,
+}
+
+#[cfg(any(feature = "ed25519", feature = "secp256k1"))]
+impl ExtendedKey {
+    /// The master key of `seed`, for the curve named by `curve`, e.g. `b"Bitcoin seed"`.
+    pub(crate) fn master(curve: &[u8], seed: &[u8]) -> Self { Self::hmac(curve, &[seed]) }
+
+    /// The child `index`, where `data` is the serialized parent key.
+    pub(crate) fn child(&self, data: &[&[u8]], index: u32) -> Self {
+        let index = index.to_be_bytes();
+        let mut parts = data.to_vec();
+        parts.push(&index);
+        Self::hmac(&*self.chain_code, &parts)
+    }
+
+    fn hmac(key: &[u8], data: &[&[u8]]) -> Self {
+        let mut ctx = hmac::Context::with_key(&hmac::Key::new(hmac::HMAC_SHA512, key));
+        for part in data {
+            ctx.update(part);
+        }
+        let tag = ctx.sign();
+        let (mut key, mut chain_code) = (Zeroizing::new([0; 32]), Zeroizing::new([0; 32]));
+        key.copy_from_slice(&tag.as_ref()[..32]);
+        chain_code.copy_from_slice(&tag.as_ref()[32..]);
+        ExtendedKey { key, chain_code }
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+
+/// A domain separation tag for hashing and signing.
+///
+/// Messages hashed or signed in a domain are prefixed with the length of the
+/// tag followed by the tag itself, so that the same bytes hashed or signed in
+/// two different domains never collide. Ed25519 uses the tag as the Ed25519ph
+/// context instead, as defined in [RFC8032].
+///
+/// Tags must not be empty, and tags starting with `RESERVED_PREFIX` are
+/// reserved for the hashes of this crate, such as the nodes of Merkle trees.
+///
+/// [RFC8032]: https://tools.ietf.org/html/rfc8032#section-5.1
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Domain {
+    len: [u8; 1],
+    tag: &'static [u8],
+}
+
+impl Domain {
+    /// The maximum length of a tag, as limited by the Ed25519ph context.
+    pub const MAX_LEN: usize = 255;
+
+    /// The prefix of the tags reserved for the domains of this crate.
+    pub const RESERVED_PREFIX: &'static str = "libcrypto/";
+
+    /// Create a domain from a tag.
+    ///
+    /// Panics if the tag is empty, longer than `MAX_LEN` bytes, or starts with
+    /// `RESERVED_PREFIX`.
+    pub const fn new(tag: &'static str) -> Self {
+        assert!(
+            !starts_with(tag.as_bytes(), Self::RESERVED_PREFIX.as_bytes()),
+            "Domain tags must not start with the reserved prefix"
+        );
+        Self::reserved(tag)
+    }
+
+    /// Create a domain from a tag, which may start with `RESERVED_PREFIX`.
+    pub(crate) const fn reserved(tag: &'static str) -> Self {
+        assert!(!tag.is_empty(), "Domain tags must not be empty");
+        assert!(tag.len() <= Self::MAX_LEN, "Domain tags are at most 255 bytes long");
+        Self { len: [tag.len() as u8], tag: tag.as_bytes() }
+    }
+
+    /// The tag of this domain.
+    pub fn tag(&self) -> &'static [u8] { self.tag }
+
+    /// The length-delimited prefix of messages in this domain.
+    pub(crate) fn prefix(&self) -> [&[u8]; 2] { [&self.len, self.tag] }
+
+    /// `msg` prefixed with the length-delimited tag of this domain.
+    pub(crate) fn tagged(&self, msg: &[u8]) -> Vec<u8> { [&self.len, self.tag, msg].concat() }
+}
+
+impl fmt::Debug for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Domain({})", String::from_utf8_lossy(self.tag))
+    }
+}
+
+const fn starts_with(bytes: &[u8], prefix: &[u8]) -> bool {
+    if bytes.len() < prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if bytes[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -2,12 +2,17 @@
 
 use core::fmt;
 
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use zeroize::ZeroizeOnDrop;
 
 use super::{PublicKey, SecretKey};
+use crate::Error;
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 /// An Ed25519 keypair.
+///
+/// Keypairs are hashed and ordered by their public key.
 pub struct Keypair {
     sk: SecretKey,
     pk: PublicKey,
@@ -15,11 +20,20 @@ pub struct Keypair {
 
 impl Keypair {
     /// Generate a new Ed25519 keypair.
-    pub fn generate() -> anyhow::Result<Keypair> {
+    pub fn generate() -> Result<Keypair, Error> {
         let sk = SecretKey::generate()?;
         Ok(Keypair::from(sk))
     }
 
+    /// Generate a new keypair from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Keypair {
+        Keypair::from(SecretKey::generate_with_rng(rng))
+    }
+
+    /// Deterministically derive a keypair from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> Keypair { Keypair::from(SecretKey::from_seed(seed)) }
+
     /// Get the public key of this keypair.
     pub fn public(&self) -> PublicKey { PublicKey(self.sk.signing_key.verifying_key()) }
 
@@ -27,6 +41,8 @@ impl Keypair {
     pub fn secret(&self) -> SecretKey { self.sk.clone() }
 }
 
+impl ZeroizeOnDrop for Keypair {}
+
 impl fmt::Debug for Keypair {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Keypair").field("public", &self.pk).field("secret", &self.sk).finish()
@@ -45,3 +61,15 @@ impl From<SecretKey> for Keypair {
         Self { sk, pk }
     }
 }
+
+impl std::hash::Hash for Keypair {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.pk.hash(state); }
+}
+
+impl PartialOrd for Keypair {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Keypair {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.pk.cmp(&other.pk) }
+}
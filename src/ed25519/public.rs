@@ -1,8 +1,12 @@
 use core::fmt;
 
-use ed25519_dalek::Verifier;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::Scalar;
+use ed25519_dalek::{Digest, Sha512, Verifier};
 use serde::{Deserialize, Serialize};
 
+use crate::{Domain, VerificationError};
+
 /// An Ed25519 public key.
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct PublicKey(pub(crate) ed25519_dalek::VerifyingKey);
@@ -17,7 +21,7 @@ impl fmt::Debug for PublicKey {
     }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
+#[allow(clippy::derived_hash_with_manual_eq)]
 impl std::hash::Hash for PublicKey {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.0.as_bytes().hash(state);
@@ -26,7 +30,7 @@ impl std::hash::Hash for PublicKey {
 
 impl PartialOrd for PublicKey {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.as_bytes().partial_cmp(other.0.as_bytes())
+        Some(self.cmp(other))
     }
 }
 
@@ -39,8 +43,32 @@ impl Ord for PublicKey {
 impl PublicKey {
     /// Verify the Ed25519 signature on a message using the public key.
     #[inline]
-    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
-        ed25519_dalek::Signature::try_from(sig).and_then(|s| self.0.verify(msg, &s)).is_ok()
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool { self.try_verify(msg, sig).is_ok() }
+
+    /// Verify the Ed25519 signature on a message like `verify`, but report why it is invalid.
+    pub fn try_verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
+        let sig = parse_signature(sig)?;
+        self.0.verify(msg, &sig).map_err(|_| VerificationError::Invalid("Ed25519"))
+    }
+
+    /// Verify an Ed25519ph signature on a message in `domain`, see
+    /// [`SecretKey::sign_with_context`](super::SecretKey::sign_with_context).
+    pub fn verify_with_context(&self, domain: &Domain, msg: &[u8], sig: &[u8]) -> bool {
+        self.try_verify_with_context(domain, msg, sig).is_ok()
+    }
+
+    /// Verify an Ed25519ph signature like `verify_with_context`, but report why it is invalid.
+    pub fn try_verify_with_context(
+        &self,
+        domain: &Domain,
+        msg: &[u8],
+        sig: &[u8],
+    ) -> Result<(), VerificationError> {
+        let sig = parse_signature(sig)?;
+        let prehashed = Sha512::new().chain_update(msg);
+        self.0
+            .verify_prehashed(prehashed, Some(domain.tag()), &sig)
+            .map_err(|_| VerificationError::Invalid("Ed25519ph"))
     }
 
     /// Batch-verify multiple (message, signature, public_key) tuples using
@@ -69,4 +97,79 @@ impl PublicKey {
             .collect();
         ed25519_dalek::verify_batch(messages, &sigs, &vks).is_ok()
     }
+
+    /// Batch-verify multiple (message, signature, public_key) tuples like
+    /// `verify_batch`, but identify the invalid signatures when the batch fails.
+    ///
+    /// A failing batch is bisected until the culprits are isolated, so a batch
+    /// with `k` bad signatures out of `n` costs `O(k log n)` batch checks. A tuple
+    /// with a missing message, signature or key is invalid.
+    ///
+    /// Returns the sorted indices of all invalid signatures on failure.
+    pub fn verify_batch_with_blame(
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&PublicKey],
+    ) -> Result<(), Vec<usize>> {
+        let complete = messages.len().min(signatures.len()).min(public_keys.len());
+        let total = messages.len().max(signatures.len()).max(public_keys.len());
+        let mut invalid: Vec<usize> = (complete..total).collect();
+
+        // Malformed signatures are blamed upfront, the rest go through bisection.
+        let mut batch = Vec::with_capacity(complete);
+        for i in 0..complete {
+            match ed25519_dalek::Signature::try_from(signatures[i]) {
+                Ok(sig) => batch.push((i, messages[i], sig, public_keys[i].0)),
+                Err(_) => invalid.push(i),
+            }
+        }
+        bisect(&batch, &mut invalid);
+
+        if invalid.is_empty() {
+            return Ok(());
+        }
+        invalid.sort_unstable();
+        Err(invalid)
+    }
+}
+
+/// Parse a 64-byte Ed25519 signature.
+///
+/// Signatures whose `s` is not a canonical scalar or whose `R` is not a point
+/// are malformed, since they can never verify.
+fn parse_signature(sig: &[u8]) -> Result<ed25519_dalek::Signature, VerificationError> {
+    let sig = ed25519_dalek::Signature::try_from(sig).map_err(|_| {
+        VerificationError::InvalidLength {
+            algorithm: "Ed25519",
+            expected: ed25519_dalek::SIGNATURE_LENGTH,
+            actual: sig.len(),
+        }
+    })?;
+    let canonical_s = bool::from(Scalar::from_canonical_bytes(*sig.s_bytes()).is_some());
+    if !canonical_s || CompressedEdwardsY(*sig.r_bytes()).decompress().is_none() {
+        return Err(VerificationError::Malformed("Ed25519 signature"));
+    }
+    Ok(sig)
+}
+
+type BatchEntry<'a> = (usize, &'a [u8], ed25519_dalek::Signature, ed25519_dalek::VerifyingKey);
+
+/// Push the indices of the invalid entries of `batch` onto `invalid`.
+fn bisect(batch: &[BatchEntry<'_>], invalid: &mut Vec<usize>) {
+    if batch.is_empty() {
+        return;
+    }
+    let messages: Vec<&[u8]> = batch.iter().map(|e| e.1).collect();
+    let sigs: Vec<ed25519_dalek::Signature> = batch.iter().map(|e| e.2).collect();
+    let vks: Vec<ed25519_dalek::VerifyingKey> = batch.iter().map(|e| e.3).collect();
+    if ed25519_dalek::verify_batch(&messages, &sigs, &vks).is_ok() {
+        return;
+    }
+    if batch.len() == 1 {
+        invalid.push(batch[0].0);
+        return;
+    }
+    let (left, right) = batch.split_at(batch.len() / 2);
+    bisect(left, invalid);
+    bisect(right, invalid);
 }
@@ -1,66 +1,108 @@
 use core::fmt;
 
-use ed25519_dalek::Signer;
+use ed25519_dalek::{Digest, Sha512, Signer};
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::derivation::ExtendedKey;
+use crate::{Algorithm, DerivationPath, Domain, Error, SigningError};
 
 #[derive(Serialize, Deserialize)]
 /// An Ed25519 secret key.
+///
+/// The key is wiped from memory when dropped.
+///
+/// Secret keys are compared in constant time, and are deliberately neither
+/// `Hash` nor `Ord`.
 pub struct SecretKey {
     pub(crate) signing_key: ed25519_dalek::SigningKey,
 }
 
-impl PartialEq for SecretKey {
-    fn eq(&self, other: &Self) -> bool {
-        self.signing_key.to_bytes() == other.signing_key.to_bytes()
-    }
-}
-
-impl Eq for SecretKey {}
-
-impl PartialOrd for SecretKey {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.signing_key.to_bytes().partial_cmp(&other.signing_key.to_bytes())
-    }
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice { self.signing_key.ct_eq(&other.signing_key) }
 }
 
-impl Ord for SecretKey {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.signing_key.to_bytes().cmp(&other.signing_key.to_bytes())
-    }
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
 }
 
-impl std::hash::Hash for SecretKey {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.signing_key.to_bytes().hash(state);
-    }
-}
+impl Eq for SecretKey {}
 
 impl SecretKey {
     pub const SIZE: usize = ed25519_dalek::SECRET_KEY_LENGTH;
 }
 
 impl Clone for SecretKey {
-    fn clone(&self) -> SecretKey {
-        let bytes = self.signing_key.to_bytes();
-        let signing_key = ed25519_dalek::SigningKey::from_bytes(&bytes);
-        Self { signing_key }
+    fn clone(&self) -> SecretKey { Self { signing_key: self.signing_key.clone() } }
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        // The replaced key is wiped when it is dropped.
+        self.signing_key = ed25519_dalek::SigningKey::from_bytes(&[0; Self::SIZE]);
     }
 }
 
+/// `ed25519_dalek::SigningKey` wipes itself on drop.
+impl ZeroizeOnDrop for SecretKey {}
+
 impl fmt::Debug for SecretKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SecretKey") }
 }
 
 impl SecretKey {
     /// Generate a new Ed25519 secret key.
-    pub fn generate() -> anyhow::Result<SecretKey> {
-        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand::thread_rng());
-        Ok(Self { signing_key })
+    pub fn generate() -> Result<SecretKey, Error> {
+        Ok(Self::generate_with_rng(&mut rand::thread_rng()))
+    }
+
+    /// Generate a new Ed25519 secret key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SecretKey {
+        Self { signing_key: ed25519_dalek::SigningKey::generate(rng) }
+    }
+
+    /// Deterministically derive a secret key from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> SecretKey {
+        Self::generate_with_rng(&mut crate::seeded_rng(seed))
+    }
+
+    /// Derive the secret key at `path` from a master `seed` as specified by
+    /// [SLIP-10]. Seeds should be between 16 and 64 bytes long.
+    ///
+    /// SLIP-10 only defines hardened derivation for Ed25519, so paths with
+    /// non-hardened indices are rejected.
+    ///
+    /// [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+    pub fn derive(seed: &[u8], path: &DerivationPath) -> Result<SecretKey, Error> {
+        if !path.indices().iter().all(|&index| DerivationPath::is_hardened(index)) {
+            return Err(Error::Unsupported {
+                algorithm: Algorithm::ED25519,
+                operation: "Non-hardened derivation",
+            });
+        }
+        let mut node = ExtendedKey::master(b"ed25519 seed", seed);
+        for &index in path.indices() {
+            node = node.child(&[&[0][..], &*node.key], index);
+        }
+        Ok(Self { signing_key: ed25519_dalek::SigningKey::from_bytes(&node.key) })
     }
 
     /// Sign a message using the private key of this keypair.
     #[inline]
-    pub fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+    pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
         Ok(self.signing_key.sign(msg).to_bytes().to_vec())
     }
+
+    /// Sign a message in `domain` with Ed25519ph, using the domain tag as the context.
+    pub fn sign_with_context(&self, domain: &Domain, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        let prehashed = Sha512::new().chain_update(msg);
+        let sig = self
+            .signing_key
+            .sign_prehashed(prehashed, Some(domain.tag()))
+            .map_err(|e| SigningError::new(Algorithm::ED25519, "Ed25519ph").source(e))?;
+        Ok(sig.to_bytes().to_vec())
+    }
 }
@@ -1,8 +1,11 @@
 use std::error::Error;
 
 use quickcheck::*;
+use zeroize::Zeroize;
 
-use crate::ed25519::Keypair;
+use crate::ed25519::{Keypair, PublicKey, SecretKey};
+use crate::tests::{assert_zeroize_on_drop, hex};
+use crate::{DerivationPath, Domain, VerificationError};
 
 fn eq_keypairs(kp1: &Keypair, kp2: &Keypair) -> bool {
     kp1.public() == kp2.public() && kp1.secret().signing_key.to_bytes() == kp2.secret().signing_key.to_bytes()
@@ -37,3 +40,108 @@ fn ed25519_signature() -> Result<(), Box<dyn Error>> {
     assert!(!pk.verify(invalid_msg, &sig));
     Ok(())
 }
+
+/// Add the group order to the `s` of `sig`, which gives a non-canonical scalar
+/// for the same point.
+fn non_canonical(sig: &[u8]) -> Vec<u8> {
+    const L: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde,
+        0x14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+    ];
+    let mut sig = sig.to_vec();
+    let mut carry = 0;
+    for (byte, l) in sig[32..].iter_mut().zip(L) {
+        let sum = *byte as u16 + l as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+    sig
+}
+
+#[test]
+fn ed25519_non_canonical_signature_is_malformed() -> Result<(), Box<dyn Error>> {
+    let kp = Keypair::generate()?;
+    let (pk, msg) = (kp.public(), b"hello world");
+    let malformed = Err(VerificationError::Malformed("Ed25519 signature"));
+
+    let sig = kp.secret().sign(msg)?;
+    assert_eq!(pk.try_verify(msg, &sig), Ok(()));
+    assert_eq!(pk.try_verify(msg, &non_canonical(&sig)), malformed);
+    let mut forged = sig.clone();
+    forged[32] ^= 1;
+    assert!(matches!(pk.try_verify(msg, &forged), Err(VerificationError::Invalid(_))));
+
+    let domain = Domain::new("test");
+    let sig = kp.secret().sign_with_context(&domain, msg)?;
+    assert_eq!(pk.try_verify_with_context(&domain, msg, &sig), Ok(()));
+    assert_eq!(pk.try_verify_with_context(&domain, msg, &non_canonical(&sig)), malformed);
+    Ok(())
+}
+
+#[test]
+fn ed25519_verify_batch_with_blame() -> Result<(), Box<dyn Error>> {
+    let kps = (0..40).map(|_| Keypair::generate()).collect::<Result<Vec<_>, _>>()?;
+    let msgs: Vec<Vec<u8>> = (0..40u8).map(|i| vec![i; 8]).collect();
+    let mut sigs = kps
+        .iter()
+        .zip(&msgs)
+        .map(|(kp, m)| kp.secret().sign(m))
+        .collect::<Result<Vec<_>, _>>()?;
+    let pks: Vec<PublicKey> = kps.iter().map(|kp| kp.public()).collect();
+    let pk_refs: Vec<&PublicKey> = pks.iter().collect();
+    let msg_refs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+
+    let sig_refs: Vec<&[u8]> = sigs.iter().map(|s| s.as_slice()).collect();
+    assert_eq!(PublicKey::verify_batch_with_blame(&msg_refs, &sig_refs, &pk_refs), Ok(()));
+
+    sigs[3] = sigs[4].clone();
+    sigs[17][0] ^= 1;
+    sigs[39].truncate(10);
+    let sig_refs: Vec<&[u8]> = sigs.iter().map(|s| s.as_slice()).collect();
+    assert!(!PublicKey::verify_batch(&msg_refs, &sig_refs, &pk_refs));
+    assert_eq!(
+        PublicKey::verify_batch_with_blame(&msg_refs, &sig_refs, &pk_refs),
+        Err(vec![3, 17, 39])
+    );
+    assert_eq!(
+        PublicKey::verify_batch_with_blame(&msg_refs[..38], &sig_refs[..37], &pk_refs[..38]),
+        Err(vec![3, 17, 37])
+    );
+    Ok(())
+}
+
+#[test]
+fn ed25519_secret_is_wiped() -> Result<(), Box<dyn Error>> {
+    let mut sk = Keypair::generate()?.secret();
+    sk.zeroize();
+    assert_eq!(sk.signing_key.to_bytes(), [0; SecretKey::SIZE]);
+
+    assert_zeroize_on_drop::<SecretKey>();
+    assert_zeroize_on_drop::<Keypair>();
+    Ok(())
+}
+
+#[test]
+fn ed25519_slip10_vectors() -> Result<(), Box<dyn Error>> {
+    // Test vector 1 of SLIP-10.
+    let seed = hex("000102030405060708090a0b0c0d0e0f");
+    let vectors = [
+        ("m", "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"),
+        ("m/0'", "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"),
+        ("m/0'/1'", "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"),
+        ("m/0'/1'/2'", "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9"),
+        ("m/0'/1'/2'/2'", "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662"),
+        (
+            "m/0'/1'/2'/2'/1000000000'",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+        ),
+    ];
+    for (path, secret) in vectors {
+        let sk = SecretKey::derive(&seed, &path.parse()?)?;
+        assert_eq!(sk.signing_key.to_bytes().to_vec(), hex(secret), "{}", path);
+    }
+
+    let path: DerivationPath = "m/0'/1".parse()?;
+    assert!(SecretKey::derive(&seed, &path).is_err());
+    Ok(())
+}
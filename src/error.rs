@@ -20,43 +20,333 @@
 
 //! Errors during identity key operations.
 
-use std::error::Error;
+use std::error::Error as StdError;
 use std::fmt;
 
+use crate::Algorithm;
+
+/// The error type of all fallible operations in this crate.
+#[derive(Debug)]
+pub enum Error {
+    /// A new key could not be generated.
+    KeyGeneration { algorithm: Algorithm, source: Box<dyn StdError + Send + Sync> },
+    /// Key material could not be decoded.
+    Decoding(DecodingError),
+    /// Key material could not be encoded.
+    Encoding { algorithm: Algorithm, source: Box<dyn StdError + Send + Sync> },
+    /// A message could not be signed.
+    Signing(SigningError),
+    /// A signature is malformed or invalid.
+    Verification(VerificationError),
+    /// A threshold signing protocol failed.
+    #[cfg(feature = "threshold")]
+    Threshold(ThresholdError),
+    /// The operation is not available for the algorithm, or the feature of the
+    /// algorithm is disabled.
+    Unsupported { algorithm: Algorithm, operation: &'static str },
+    /// A ciphertext could not be decrypted.
+    Decryption(&'static str),
+    /// A secret could not be shared or reconstructed.
+    Sharing(SharingError),
+    /// A key exchange produced no usable shared secret.
+    #[cfg(feature = "x25519")]
+    KeyExchange(&'static str),
+}
+
+impl Error {
+    /// The algorithm of the key involved in the failed operation, if known.
+    pub fn algorithm(&self) -> Option<Algorithm> {
+        match self {
+            Error::KeyGeneration { algorithm, .. } => Some(*algorithm),
+            Error::Decoding(e) => e.algorithm(),
+            Error::Encoding { algorithm, .. } => Some(*algorithm),
+            Error::Signing(e) => Some(e.algorithm()),
+            Error::Verification(_) => None,
+            #[cfg(feature = "threshold")]
+            Error::Threshold(_) => Some(Algorithm::BLS),
+            Error::Unsupported { algorithm, .. } => Some(*algorithm),
+            Error::Decryption(_) => None,
+            Error::Sharing(_) => None,
+            #[cfg(feature = "x25519")]
+            Error::KeyExchange(_) => None,
+        }
+    }
+
+    #[cfg_attr(not(any(feature = "rsa", feature = "secp256k1")), allow(dead_code))]
+    pub(crate) fn key_generation(
+        algorithm: Algorithm,
+        source: impl StdError + Send + Sync + 'static,
+    ) -> Self {
+        Error::KeyGeneration { algorithm, source: Box::new(source) }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::KeyGeneration { algorithm, .. } => {
+                write!(f, "Key generation error: {:?}", algorithm)
+            }
+            Error::Decoding(e) => e.fmt(f),
+            Error::Encoding { algorithm, source } => {
+                write!(f, "Encoding error: {:?}: {}", algorithm, source)
+            }
+            Error::Signing(e) => e.fmt(f),
+            Error::Verification(e) => e.fmt(f),
+            #[cfg(feature = "threshold")]
+            Error::Threshold(e) => e.fmt(f),
+            Error::Unsupported { algorithm, operation } => {
+                write!(f, "{} is not supported for {:?}", operation, algorithm)
+            }
+            Error::Decryption(reason) => write!(f, "Decryption error: {}", reason),
+            Error::Sharing(e) => e.fmt(f),
+            #[cfg(feature = "x25519")]
+            Error::KeyExchange(reason) => write!(f, "Key exchange error: {}", reason),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::KeyGeneration { source, .. } => Some(&**source as &dyn StdError),
+            Error::Decoding(e) => Some(e),
+            Error::Encoding { source, .. } => Some(&**source as &dyn StdError),
+            Error::Signing(e) => Some(e),
+            Error::Verification(e) => Some(e),
+            #[cfg(feature = "threshold")]
+            Error::Threshold(e) => Some(e),
+            Error::Unsupported { .. } => None,
+            Error::Decryption(_) => None,
+            Error::Sharing(e) => Some(e),
+            #[cfg(feature = "x25519")]
+            Error::KeyExchange(_) => None,
+        }
+    }
+}
+
+impl From<DecodingError> for Error {
+    fn from(e: DecodingError) -> Self { Error::Decoding(e) }
+}
+
+impl From<SigningError> for Error {
+    fn from(e: SigningError) -> Self { Error::Signing(e) }
+}
+
+impl From<VerificationError> for Error {
+    fn from(e: VerificationError) -> Self { Error::Verification(e) }
+}
+
+#[cfg(feature = "threshold")]
+impl From<ThresholdError> for Error {
+    fn from(e: ThresholdError) -> Self { Error::Threshold(e) }
+}
+
+impl From<SharingError> for Error {
+    fn from(e: SharingError) -> Self { Error::Sharing(e) }
+}
+
 /// An error during decoding of key material.
 #[derive(Debug)]
 pub struct DecodingError {
+    algorithm: Option<Algorithm>,
     msg: String,
-    source: Option<Box<dyn Error + Send + Sync>>,
+    source: Option<Box<dyn StdError + Send + Sync>>,
+}
+
+impl DecodingError {
+    /// Create an error for key material of `algorithm` that could not be decoded.
+    pub fn new<S: ToString>(algorithm: Algorithm, msg: S) -> Self {
+        Self { algorithm: Some(algorithm), msg: msg.to_string(), source: None }
+    }
+
+    /// Create an error for key material whose algorithm could not be determined.
+    pub fn unknown_algorithm<S: ToString>(msg: S) -> Self {
+        Self { algorithm: None, msg: msg.to_string(), source: None }
+    }
+
+    /// Attach the underlying error.
+    pub fn source(self, source: impl StdError + Send + Sync + 'static) -> Self {
+        Self { source: Some(Box::new(source)), ..self }
+    }
+
+    /// The algorithm of the key material, if known.
+    pub fn algorithm(&self) -> Option<Algorithm> { self.algorithm }
 }
 
 impl fmt::Display for DecodingError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Key decoding error: {}", self.msg)
+        match self.algorithm {
+            Some(algorithm) => write!(f, "Key decoding error ({:?}): {}", algorithm, self.msg),
+            None => write!(f, "Key decoding error: {}", self.msg),
+        }
     }
 }
 
-impl Error for DecodingError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.source.as_ref().map(|s| &**s as &dyn Error)
+impl StdError for DecodingError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.as_ref().map(|s| &**s as &dyn StdError)
     }
 }
 
 /// An error during signing of a message.
 #[derive(Debug)]
 pub struct SigningError {
+    algorithm: Algorithm,
     msg: String,
-    source: Option<Box<dyn Error + Send + Sync>>,
+    source: Option<Box<dyn StdError + Send + Sync>>,
+}
+
+impl SigningError {
+    /// Create an error for a message that could not be signed with a key of `algorithm`.
+    pub fn new<S: ToString>(algorithm: Algorithm, msg: S) -> Self {
+        Self { algorithm, msg: msg.to_string(), source: None }
+    }
+
+    /// Attach the underlying error.
+    pub fn source(self, source: impl StdError + Send + Sync + 'static) -> Self {
+        Self { source: Some(Box::new(source)), ..self }
+    }
+
+    /// The algorithm of the signing key.
+    pub fn algorithm(&self) -> Algorithm { self.algorithm }
 }
 
 impl fmt::Display for SigningError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Key signing error: {}", self.msg)
+        write!(f, "Key signing error ({:?}): {}", self.algorithm, self.msg)
+    }
+}
+
+impl StdError for SigningError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.as_ref().map(|s| &**s as &dyn StdError)
+    }
+}
+
+/// An error during verification of a signature.
+///
+/// Distinguishes signatures that cannot even be parsed from well-formed
+/// signatures that do not match the message and key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The signature does not have the length required by the algorithm.
+    InvalidLength { algorithm: &'static str, expected: usize, actual: usize },
+    /// The signature (or prehashed message) is not a valid encoding, e.g.
+    /// malformed DER or a point that is not on the curve.
+    Malformed(&'static str),
+    /// The signature has been produced by a key of another algorithm.
+    AlgorithmMismatch,
+    /// The message, signature or key is missing from a batch.
+    MissingInput,
+    /// The signature is well-formed, but not valid for the message and key.
+    Invalid(&'static str),
+    /// The payload has been signed by another key than the expected one.
+    UnexpectedSigner,
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use VerificationError::*;
+        match self {
+            InvalidLength { algorithm, expected, actual } => write!(
+                f,
+                "Signature verification error: {} signatures are {} bytes, got {}",
+                algorithm, expected, actual
+            ),
+            Malformed(what) => write!(f, "Signature verification error: malformed {}", what),
+            AlgorithmMismatch => {
+                write!(f, "Signature verification error: signature and key algorithms differ")
+            }
+            MissingInput => {
+                write!(f, "Signature verification error: missing message, signature or key")
+            }
+            Invalid(algorithm) => {
+                write!(f, "Signature verification error: invalid {} signature", algorithm)
+            }
+            UnexpectedSigner => write!(f, "Signature verification error: unexpected signer"),
+        }
     }
 }
 
-impl Error for SigningError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.source.as_ref().map(|s| &**s as &dyn Error)
+impl StdError for VerificationError {}
+
+/// An error in a threshold signing protocol.
+#[cfg(feature = "threshold")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThresholdError {
+    /// The threshold is zero or larger than the number of nodes.
+    InvalidThreshold { threshold: usize, nodes: usize },
+    /// A node index is out of range.
+    InvalidIndex { index: usize, nodes: usize },
+    /// Fewer shares or dealers than the threshold.
+    NotEnoughShares { threshold: usize, got: usize },
+    /// A share is meant for another node.
+    WrongRecipient { index: usize },
+    /// The commitment of a dealer has the wrong degree.
+    InvalidCommitment { dealer: usize },
+    /// The share from a node or dealer does not match its public key or commitment.
+    InvalidShare { index: usize },
+    /// No valid share has been received from a dealer.
+    MissingShare { dealer: usize },
+}
+
+#[cfg(feature = "threshold")]
+impl fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ThresholdError::*;
+        write!(f, "Threshold error: ")?;
+        match self {
+            InvalidThreshold { threshold, nodes } => {
+                write!(f, "invalid threshold {} for {} nodes", threshold, nodes)
+            }
+            InvalidIndex { index, nodes } => {
+                write!(f, "invalid index {} for {} nodes", index, nodes)
+            }
+            NotEnoughShares { threshold, got } => {
+                write!(f, "need at least {} shares, got {}", threshold, got)
+            }
+            WrongRecipient { index } => write!(f, "share is meant for node {}", index),
+            InvalidCommitment { dealer } => {
+                write!(f, "commitment of dealer {} has the wrong degree", dealer)
+            }
+            InvalidShare { index } => write!(f, "invalid share from node {}", index),
+            MissingShare { dealer } => write!(f, "no valid share from dealer {}", dealer),
+        }
     }
 }
+
+#[cfg(feature = "threshold")]
+impl StdError for ThresholdError {}
+
+/// An error in secret sharing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SharingError {
+    /// The threshold is zero or larger than the number of nodes.
+    InvalidThreshold { threshold: usize, nodes: usize },
+    /// Fewer shares than the threshold.
+    NotEnoughShares { threshold: usize, got: usize },
+    /// Two shares are held by the same node.
+    DuplicateShare { index: usize },
+    /// The shares do not belong to the same secret, or some are corrupted.
+    InconsistentShares,
+}
+
+impl fmt::Display for SharingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use SharingError::*;
+        write!(f, "Sharing error: ")?;
+        match self {
+            InvalidThreshold { threshold, nodes } => {
+                write!(f, "invalid threshold {} for {} nodes", threshold, nodes)
+            }
+            NotEnoughShares { threshold, got } => {
+                write!(f, "need at least {} shares, got {}", threshold, got)
+            }
+            DuplicateShare { index } => write!(f, "duplicate share of node {}", index),
+            InconsistentShares => write!(f, "the shares are inconsistent"),
+        }
+    }
+}
+
+impl StdError for SharingError {}
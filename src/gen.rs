@@ -18,17 +18,32 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
-use serde::{Deserialize, Serialize};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(any(feature = "rsa", feature = "p256"))]
+use zeroize::Zeroize;
 
+#[cfg(feature = "bls")]
+use crate::bls;
 #[cfg(feature = "ed25519")]
 use crate::ed25519;
+#[cfg(feature = "p256")]
+use crate::p256;
 #[cfg(feature = "rsa")]
 use crate::rsa;
 #[cfg(feature = "secp256k1")]
 use crate::secp256k1;
+#[cfg(feature = "x25519")]
+use crate::x25519;
+use crate::{Algorithm, DerivationPath, Domain, Error, VerificationError};
 
 /// Identity keypair of a node.
 ///
+/// Keypairs are compared in constant time, but hashed and ordered by their
+/// public key, so they can be used as map keys without leaking the secret key.
+///
 /// # Example: Generating RSA keys with OpenSSL
 ///
 /// ```text
@@ -57,9 +72,25 @@ pub enum Keypair {
     /// A Secp256k1 keypair.
     #[cfg(feature = "secp256k1")]
     Secp256k1(secp256k1::Keypair),
+
+    /// A BLS12-381 keypair.
+    #[cfg(feature = "bls")]
+    Bls(bls::Keypair),
+
+    /// A P-256 keypair.
+    #[cfg(feature = "p256")]
+    P256(p256::Keypair),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// The RNG behind all `from_seed` constructors.
+pub(crate) fn seeded_rng(seed: &[u8; 32]) -> ChaCha20Rng { ChaCha20Rng::from_seed(*seed) }
+
+/// The secret key of any supported algorithm.
+///
+/// Secret keys are compared in constant time and are deliberately neither
+/// `Hash` nor `Ord`. Use a [`Keypair`], which is hashed and ordered by its
+/// public key, as a map key instead.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SecretKey {
     /// An Ed25519 keypair.
     #[cfg(feature = "ed25519")]
@@ -70,28 +101,121 @@ pub enum SecretKey {
     /// A Secp256k1 keypair.
     #[cfg(feature = "secp256k1")]
     Secp256k1(secp256k1::SecretKey),
+    /// A BLS12-381 keypair.
+    #[cfg(feature = "bls")]
+    Bls(bls::SecretKey),
+    /// A P-256 keypair.
+    #[cfg(feature = "p256")]
+    P256(p256::SecretKey),
 }
 
 impl SecretKey {
+    /// The algorithm of this secret key.
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            #[cfg(feature = "ed25519")]
+            SecretKey::Ed25519(_) => Algorithm::ED25519,
+            #[cfg(feature = "rsa")]
+            SecretKey::Rsa(_) => Algorithm::RSA,
+            #[cfg(feature = "secp256k1")]
+            SecretKey::Secp256k1(_) => Algorithm::SECP256K1,
+            #[cfg(feature = "bls")]
+            SecretKey::Bls(_) => Algorithm::BLS,
+            #[cfg(feature = "p256")]
+            SecretKey::P256(_) => Algorithm::P256,
+        }
+    }
+
+    /// Decrypt a ciphertext of [`PublicKey::encrypt`] encrypted with the same
+    /// `aad`.
+    ///
+    /// Fails with [`Error::Unsupported`] for algorithms that cannot encrypt.
+    #[cfg_attr(not(any(feature = "x25519", feature = "secp256k1")), allow(unused_variables))]
+    pub fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            #[cfg(feature = "x25519")]
+            SecretKey::Ed25519(key) => x25519::SecretKey::from(key).decrypt(ciphertext, aad),
+            #[cfg(feature = "secp256k1")]
+            SecretKey::Secp256k1(key) => key.decrypt(ciphertext, aad),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::Unsupported { algorithm: self.algorithm(), operation: "Encryption" }),
+        }
+    }
+
     /// Sign a message using the private key of this keypair, producing
     /// a signature that can be verified using the corresponding public key.
     #[inline]
-    pub fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+    pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
         match self {
             #[cfg(feature = "ed25519")]
-            SecretKey::Ed25519(ref key) => Ok(key.sign(msg)?),
+            SecretKey::Ed25519(ref key) => key.sign(msg),
             #[cfg(feature = "rsa")]
             SecretKey::Rsa(ref key) => key.sign(msg),
             #[cfg(feature = "secp256k1")]
             SecretKey::Secp256k1(ref pair) => pair.sign(msg),
+            #[cfg(feature = "bls")]
+            SecretKey::Bls(ref key) => key.sign(msg),
+            #[cfg(feature = "p256")]
+            SecretKey::P256(ref key) => key.sign(msg),
+        }
+    }
+
+    /// Sign a message like [`SecretKey::sign`], but tag the signature with its algorithm.
+    pub fn sign_typed(&self, msg: &[u8]) -> Result<Signature, Error> {
+        let sig = self.sign(msg)?;
+        Ok(match self {
+            #[cfg(feature = "ed25519")]
+            SecretKey::Ed25519(_) => Signature::Ed25519(sig),
+            #[cfg(feature = "rsa")]
+            SecretKey::Rsa(_) => Signature::Rsa(sig),
+            #[cfg(feature = "secp256k1")]
+            SecretKey::Secp256k1(_) => Signature::Secp256k1(sig),
+            #[cfg(feature = "bls")]
+            SecretKey::Bls(_) => Signature::Bls(sig),
+            #[cfg(feature = "p256")]
+            SecretKey::P256(_) => Signature::P256(sig),
+        })
+    }
+
+    /// Sign a message in `domain`, so that the signature does not verify for
+    /// the same message in any other domain.
+    ///
+    /// Ed25519 keys sign with Ed25519ph using the domain tag as the context,
+    /// other keys sign the message prefixed with the length-delimited tag.
+    pub fn sign_with_context(&self, domain: &Domain, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            #[cfg(feature = "ed25519")]
+            SecretKey::Ed25519(ref key) => key.sign_with_context(domain, msg),
+            #[allow(unreachable_patterns)]
+            _ => self.sign(&domain.tagged(msg)),
         }
     }
 }
 
 impl Keypair {
+    /// Generate a new keypair of `algorithm`.
+    ///
+    /// Fails if the feature of `algorithm` is disabled.
+    pub fn generate(algorithm: Algorithm) -> Result<Keypair, Error> {
+        match algorithm {
+            #[cfg(feature = "ed25519")]
+            Algorithm::ED25519 => Self::generate_ed25519(),
+            #[cfg(feature = "rsa")]
+            Algorithm::RSA => Self::generate_rsa(),
+            #[cfg(feature = "secp256k1")]
+            Algorithm::SECP256K1 => Ok(Self::generate_secp256k1()),
+            #[cfg(feature = "bls")]
+            Algorithm::BLS => Ok(Self::generate_bls()),
+            #[cfg(feature = "p256")]
+            Algorithm::P256 => Ok(Self::generate_p256()),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::Unsupported { algorithm, operation: "Key generation" }),
+        }
+    }
+
     /// Generate a new Ed25519 keypair.
     #[cfg(feature = "ed25519")]
-    pub fn generate_ed25519() -> anyhow::Result<Keypair> {
+    pub fn generate_ed25519() -> Result<Keypair, Error> {
         let kp = ed25519::Keypair::generate()?;
         Ok(Keypair::Ed25519(Box::new(kp)))
     }
@@ -100,13 +224,103 @@ impl Keypair {
     #[cfg(feature = "secp256k1")]
     pub fn generate_secp256k1() -> Keypair { Keypair::Secp256k1(secp256k1::Keypair::generate()) }
 
+    /// Generate a new BLS12-381 keypair.
+    #[cfg(feature = "bls")]
+    pub fn generate_bls() -> Keypair { Keypair::Bls(bls::Keypair::generate()) }
+
+    /// Generate a new P-256 keypair.
+    #[cfg(feature = "p256")]
+    pub fn generate_p256() -> Keypair { Keypair::P256(p256::Keypair::generate()) }
+
+    /// Generate a new RSA keypair.
+    #[cfg(feature = "rsa")]
+    pub fn generate_rsa() -> Result<Keypair, Error> {
+        let kp = rsa::Keypair::generate()?;
+        Ok(Keypair::Rsa(kp))
+    }
+
+    /// Generate a new keypair of `algorithm` from `rng`.
+    ///
+    /// Fails for RSA, whose keys are only generated by OpenSSL from its own
+    /// randomness, and if the feature of `algorithm` is disabled.
+    #[cfg_attr(
+        not(any(feature = "ed25519", feature = "secp256k1", feature = "bls", feature = "p256")),
+        allow(unused_variables)
+    )]
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(
+        algorithm: Algorithm,
+        rng: &mut R,
+    ) -> Result<Keypair, Error> {
+        match algorithm {
+            #[cfg(feature = "ed25519")]
+            Algorithm::ED25519 => {
+                Ok(Keypair::Ed25519(Box::new(ed25519::Keypair::generate_with_rng(rng))))
+            }
+            #[cfg(feature = "rsa")]
+            Algorithm::RSA => {
+                Err(Error::Unsupported { algorithm, operation: "Key generation from an RNG" })
+            }
+            #[cfg(feature = "secp256k1")]
+            Algorithm::SECP256K1 => {
+                Ok(Keypair::Secp256k1(secp256k1::Keypair::generate_with_rng(rng)))
+            }
+            #[cfg(feature = "bls")]
+            Algorithm::BLS => Ok(Keypair::Bls(bls::Keypair::generate_with_rng(rng))),
+            #[cfg(feature = "p256")]
+            Algorithm::P256 => Ok(Keypair::P256(p256::Keypair::generate_with_rng(rng))),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::Unsupported { algorithm, operation: "Key generation" }),
+        }
+    }
+
+    /// Deterministically derive a keypair of `algorithm` from a 32-byte seed.
+    ///
+    /// The seed drives a ChaCha20 RNG passed to `generate_with_rng`, so the same
+    /// seed always yields the same keypair. This is meant for reproducible
+    /// simulations and testnets, e.g. deriving the key of node `i` from the hash
+    /// of a master seed and `i`. Anyone who knows the seed knows the secret key.
+    ///
+    /// Fails for RSA, see `generate_with_rng`.
+    pub fn from_seed(algorithm: Algorithm, seed: &[u8; 32]) -> Result<Keypair, Error> {
+        Self::generate_with_rng(algorithm, &mut seeded_rng(seed))
+    }
+
+    /// Derive the keypair of `algorithm` at `path` from a master `seed`, e.g.
+    /// one key per purpose under `m/44'/0'/0'`, `m/44'/0'/1'` and so on.
+    ///
+    /// Ed25519 keys are derived as specified by SLIP-10, which only supports
+    /// hardened paths, and Secp256k1 keys as specified by BIP-32. Other
+    /// algorithms fail with [`Error::Unsupported`].
+    #[cfg_attr(not(any(feature = "ed25519", feature = "secp256k1")), allow(unused_variables))]
+    pub fn derive(
+        algorithm: Algorithm,
+        seed: &[u8],
+        path: &DerivationPath,
+    ) -> Result<Keypair, Error> {
+        match algorithm {
+            #[cfg(feature = "ed25519")]
+            Algorithm::ED25519 => {
+                let sk = ed25519::SecretKey::derive(seed, path)?;
+                Ok(Keypair::Ed25519(Box::new(ed25519::Keypair::from(sk))))
+            }
+            #[cfg(feature = "secp256k1")]
+            Algorithm::SECP256K1 => {
+                let sk = secp256k1::SecretKey::derive(seed, path)?;
+                Ok(Keypair::Secp256k1(secp256k1::Keypair::from(sk)))
+            }
+            _ => Err(Error::Unsupported { algorithm, operation: "HD derivation" }),
+        }
+    }
+
     /// Decode an keypair from a DER-encoded secret key in PKCS#8 PrivateKeyInfo
-    /// format (i.e. unencrypted) as defined in [RFC5208].
+    /// format (i.e. unencrypted) as defined in [RFC5208], zeroing the input on success.
     ///
     /// [RFC5208]: https://tools.ietf.org/html/rfc5208#section-5
     #[cfg(feature = "rsa")]
-    pub fn rsa_from_pkcs8(pkcs8_der: &mut [u8]) -> Result<Keypair, DecodingError> {
-        rsa::Keypair::from_pkcs8(pkcs8_der).map(Keypair::Rsa)
+    pub fn rsa_from_pkcs8(pkcs8_der: &mut [u8]) -> Result<Keypair, Error> {
+        let keypair = rsa::Keypair::from_pkcs8(pkcs8_der)?;
+        pkcs8_der.zeroize();
+        Ok(Keypair::Rsa(keypair))
     }
 
     pub fn private(&self) -> SecretKey {
@@ -117,19 +331,50 @@ impl Keypair {
             Self::Rsa(kpair) => SecretKey::Rsa(kpair.secret()),
             #[cfg(feature = "secp256k1")]
             Self::Secp256k1(kpair) => SecretKey::Secp256k1(kpair.secret().clone()),
+            #[cfg(feature = "bls")]
+            Self::Bls(kpair) => SecretKey::Bls(kpair.secret().clone()),
+            #[cfg(feature = "p256")]
+            Self::P256(kpair) => SecretKey::P256(kpair.secret().clone()),
         }
     }
 
     /// Decode a keypair from a DER-encoded Secp256k1 secret key in an ECPrivateKey
-    /// structure as defined in [RFC5915].
+    /// structure as defined in [RFC5915], zeroing the input on success.
     ///
     /// [RFC5915]: https://tools.ietf.org/html/rfc5915
     #[cfg(feature = "secp256k1")]
-    pub fn secp256k1_from_der(der: &mut [u8]) -> anyhow::Result<Keypair> {
+    pub fn secp256k1_from_der(der: &mut [u8]) -> Result<Keypair, Error> {
         let sk = secp256k1::SecretKey::from_der(der)?;
         Ok(Keypair::Secp256k1(secp256k1::Keypair::from(sk)))
     }
 
+    /// Decode a keypair from a DER-encoded P-256 secret key in an ECPrivateKey
+    /// structure as defined in [RFC5915], zeroing the input on success.
+    ///
+    /// [RFC5915]: https://tools.ietf.org/html/rfc5915
+    #[cfg(feature = "p256")]
+    pub fn p256_from_der(der: &mut [u8]) -> Result<Keypair, Error> {
+        let sk = p256::SecretKey::from_der(&*der)?;
+        der.zeroize();
+        Ok(Keypair::P256(p256::Keypair::from(sk)))
+    }
+
+    /// The algorithm of this keypair.
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            #[cfg(feature = "ed25519")]
+            Keypair::Ed25519(_) => Algorithm::ED25519,
+            #[cfg(feature = "rsa")]
+            Keypair::Rsa(_) => Algorithm::RSA,
+            #[cfg(feature = "secp256k1")]
+            Keypair::Secp256k1(_) => Algorithm::SECP256K1,
+            #[cfg(feature = "bls")]
+            Keypair::Bls(_) => Algorithm::BLS,
+            #[cfg(feature = "p256")]
+            Keypair::P256(_) => Algorithm::P256,
+        }
+    }
+
     /// Get the public key of this keypair.
     pub fn public(&self) -> PublicKey {
         use Keypair::*;
@@ -140,12 +385,38 @@ impl Keypair {
             Rsa(pair) => PublicKey::Rsa(pair.public()),
             #[cfg(feature = "secp256k1")]
             Secp256k1(pair) => PublicKey::Secp256k1(pair.public().clone()),
+            #[cfg(feature = "bls")]
+            Bls(pair) => PublicKey::Bls(*pair.public()),
+            #[cfg(feature = "p256")]
+            P256(pair) => PublicKey::P256(*pair.public()),
+        }
+    }
+}
+
+/// Complete a secret key with its public key.
+impl From<SecretKey> for Keypair {
+    fn from(sk: SecretKey) -> Keypair {
+        match sk {
+            #[cfg(feature = "ed25519")]
+            SecretKey::Ed25519(sk) => Keypair::Ed25519(Box::new(sk.into())),
+            #[cfg(feature = "rsa")]
+            SecretKey::Rsa(sk) => Keypair::Rsa(sk.0),
+            #[cfg(feature = "secp256k1")]
+            SecretKey::Secp256k1(sk) => Keypair::Secp256k1(sk.into()),
+            #[cfg(feature = "bls")]
+            SecretKey::Bls(sk) => Keypair::Bls(sk.into()),
+            #[cfg(feature = "p256")]
+            SecretKey::P256(sk) => Keypair::P256(sk.into()),
         }
     }
 }
 
 /// The public key of a node's identity keypair.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash, PartialOrd, Ord)]
+///
+/// Public keys serialize as their multicodec encoding, see
+/// [`PublicKey::to_multicodec`], which is the same whichever cargo features
+/// are enabled.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum PublicKey {
     /// A public Ed25519 key.
     #[cfg(feature = "ed25519")]
@@ -156,45 +427,295 @@ pub enum PublicKey {
     /// A public Secp256k1 key.
     #[cfg(feature = "secp256k1")]
     Secp256k1(secp256k1::PublicKey),
+    /// A public BLS12-381 key.
+    #[cfg(feature = "bls")]
+    Bls(bls::PublicKey),
+    /// A public P-256 key.
+    #[cfg(feature = "p256")]
+    P256(p256::PublicKey),
 }
 
 impl PublicKey {
+    /// The algorithm of this public key.
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            #[cfg(feature = "ed25519")]
+            PublicKey::Ed25519(_) => Algorithm::ED25519,
+            #[cfg(feature = "rsa")]
+            PublicKey::Rsa(_) => Algorithm::RSA,
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(_) => Algorithm::SECP256K1,
+            #[cfg(feature = "bls")]
+            PublicKey::Bls(_) => Algorithm::BLS,
+            #[cfg(feature = "p256")]
+            PublicKey::P256(_) => Algorithm::P256,
+        }
+    }
+
+    /// Encrypt `msg` to the holder of the secret key, authenticating `aad`
+    /// along with it. The ciphertext is decrypted with [`SecretKey::decrypt`].
+    ///
+    /// Ed25519 keys are converted to X25519 keys and use HPKE as specified by
+    /// RFC 9180, with X25519, HKDF-SHA256 and ChaCha20-Poly1305, which requires
+    /// the `x25519` feature. Secp256k1 keys use ECIES, see
+    /// [`secp256k1::PublicKey::encrypt`]. Other algorithms fail with
+    /// [`Error::Unsupported`].
+    #[cfg_attr(not(any(feature = "x25519", feature = "secp256k1")), allow(unused_variables))]
+    pub fn encrypt(&self, msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            #[cfg(feature = "x25519")]
+            PublicKey::Ed25519(pk) => x25519::PublicKey::from(pk).encrypt(msg, aad),
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(pk) => Ok(pk.encrypt(msg, aad)),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::Unsupported { algorithm: self.algorithm(), operation: "Encryption" }),
+        }
+    }
+
     /// Verify a signature for a message using this public key, i.e. check
     /// that the signature has been produced by the corresponding
     /// private key (authenticity), and that the message has not been
     /// tampered with (integrity).
     #[inline]
-    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool { self.try_verify(msg, sig).is_ok() }
+
+    /// Verify a signature like `verify`, but tell malformed signatures apart
+    /// from well-formed signatures that do not match the message and key.
+    pub fn try_verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
         use PublicKey::*;
         match self {
             #[cfg(feature = "ed25519")]
-            Ed25519(pk) => pk.verify(msg, sig),
+            Ed25519(pk) => pk.try_verify(msg, sig),
             #[cfg(feature = "rsa")]
-            Rsa(pk) => pk.verify(msg, sig),
+            Rsa(pk) => pk.try_verify(msg, sig),
             #[cfg(feature = "secp256k1")]
-            Secp256k1(pk) => pk.verify(msg, sig),
+            Secp256k1(pk) => pk.try_verify(msg, sig),
+            #[cfg(feature = "bls")]
+            Bls(pk) => pk.try_verify(msg, sig),
+            #[cfg(feature = "p256")]
+            P256(pk) => pk.try_verify(msg, sig),
         }
     }
 
-    /// Batch-verify multiple (message, signature, public_key) tuples.
-    /// All keys must be Ed25519. Returns false if any key is not Ed25519.
-    #[cfg(feature = "ed25519")]
+    /// Verify a typed signature for a message using this public key.
+    ///
+    /// A signature produced by a key of a different algorithm is invalid.
+    pub fn verify_signature(&self, msg: &[u8], sig: &Signature) -> bool {
+        self.try_verify_signature(msg, sig).is_ok()
+    }
+
+    /// Verify a typed signature like `verify_signature`, but report why it is invalid.
+    pub fn try_verify_signature(
+        &self,
+        msg: &[u8],
+        sig: &Signature,
+    ) -> Result<(), VerificationError> {
+        match (self, sig) {
+            #[cfg(feature = "ed25519")]
+            (PublicKey::Ed25519(pk), Signature::Ed25519(sig)) => pk.try_verify(msg, sig),
+            #[cfg(feature = "rsa")]
+            (PublicKey::Rsa(pk), Signature::Rsa(sig)) => pk.try_verify(msg, sig),
+            #[cfg(feature = "secp256k1")]
+            (PublicKey::Secp256k1(pk), Signature::Secp256k1(sig)) => pk.try_verify(msg, sig),
+            #[cfg(feature = "bls")]
+            (PublicKey::Bls(pk), Signature::Bls(sig)) => pk.try_verify(msg, sig),
+            #[cfg(feature = "p256")]
+            (PublicKey::P256(pk), Signature::P256(sig)) => pk.try_verify(msg, sig),
+            #[allow(unreachable_patterns)]
+            _ => Err(VerificationError::AlgorithmMismatch),
+        }
+    }
+
+    /// Verify a signature on a message in `domain`, produced by
+    /// [`SecretKey::sign_with_context`].
+    pub fn verify_with_context(&self, domain: &Domain, msg: &[u8], sig: &[u8]) -> bool {
+        self.try_verify_with_context(domain, msg, sig).is_ok()
+    }
+
+    /// Verify a signature in `domain` like `verify_with_context`, but report why it is invalid.
+    pub fn try_verify_with_context(
+        &self,
+        domain: &Domain,
+        msg: &[u8],
+        sig: &[u8],
+    ) -> Result<(), VerificationError> {
+        match self {
+            #[cfg(feature = "ed25519")]
+            PublicKey::Ed25519(pk) => pk.try_verify_with_context(domain, msg, sig),
+            #[allow(unreachable_patterns)]
+            _ => self.try_verify(&domain.tagged(msg), sig),
+        }
+    }
+
+    /// Batch-verify multiple (message, signature, public_key) tuples with keys of
+    /// any algorithm.
+    ///
+    /// Tuples are grouped by algorithm. Groups with native batch verification
+    /// (Ed25519) are checked in one go and bisected on failure, the rest are
+    /// verified individually in parallel. A tuple with a missing message,
+    /// signature or key is invalid.
+    ///
+    /// Returns the sorted indices of all invalid signatures on failure.
     pub fn verify_batch(
         messages: &[&[u8]],
         signatures: &[&[u8]],
         public_keys: &[&PublicKey],
-    ) -> bool {
-        let ed_keys: Vec<&ed25519::PublicKey> = match public_keys
-            .iter()
-            .map(|pk| match pk {
-                PublicKey::Ed25519(k) => Some(k),
+    ) -> Result<(), Vec<usize>> {
+        let complete = messages.len().min(signatures.len()).min(public_keys.len());
+        let total = messages.len().max(signatures.len()).max(public_keys.len());
+        let mut invalid: Vec<usize> = (complete..total).collect();
+
+        let mut individual = Vec::new();
+        #[cfg(feature = "ed25519")]
+        let mut ed25519_batch = Vec::new();
+        for (i, pk) in public_keys.iter().take(complete).enumerate() {
+            match pk {
+                #[cfg(feature = "ed25519")]
+                PublicKey::Ed25519(pk) => ed25519_batch.push((i, pk)),
                 #[allow(unreachable_patterns)]
-                _ => None,
-            })
-            .collect::<Option<Vec<_>>>() {
-                Some(keys) => keys,
-                None => return false,
-            };
-        ed25519::PublicKey::verify_batch(messages, signatures, &ed_keys)
+                _ => individual.push(i),
+            }
+        }
+
+        #[cfg(feature = "ed25519")]
+        if !ed25519_batch.is_empty() {
+            let msgs: Vec<&[u8]> = ed25519_batch.iter().map(|(i, _)| messages[*i]).collect();
+            let sigs: Vec<&[u8]> = ed25519_batch.iter().map(|(i, _)| signatures[*i]).collect();
+            let keys: Vec<&ed25519::PublicKey> = ed25519_batch.iter().map(|(_, pk)| *pk).collect();
+            let result = ed25519::PublicKey::verify_batch_with_blame(&msgs, &sigs, &keys);
+            if let Err(bad) = result {
+                invalid.extend(bad.into_iter().map(|j| ed25519_batch[j].0));
+            }
+        }
+
+        invalid.par_extend(
+            individual
+                .into_par_iter()
+                .filter(|&i| !public_keys[i].verify(messages[i], signatures[i])),
+        );
+        if invalid.is_empty() {
+            return Ok(());
+        }
+        invalid.sort_unstable();
+        Err(invalid)
+    }
+
+    /// Batch-verify like `verify_batch`, but report why each invalid signature is invalid.
+    ///
+    /// Returns the sorted indices of all invalid signatures along with their errors on failure.
+    pub fn try_verify_batch(
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&PublicKey],
+    ) -> Result<(), Vec<(usize, VerificationError)>> {
+        let invalid = match Self::verify_batch(messages, signatures, public_keys) {
+            Ok(()) => return Ok(()),
+            Err(invalid) => invalid,
+        };
+        // Only the culprits are verified again, to find out what is wrong with them.
+        let blame = |i: usize| match (messages.get(i), signatures.get(i), public_keys.get(i)) {
+            (Some(msg), Some(sig), Some(pk)) => match pk.try_verify(msg, sig) {
+                Err(e) => e,
+                // Batched Ed25519 verification is slightly stricter than single verification.
+                Ok(()) => VerificationError::Invalid("Ed25519"),
+            },
+            _ => VerificationError::MissingInput,
+        };
+        Err(invalid.into_iter().map(|i| (i, blame(i))).collect())
+    }
+}
+
+/// A signature produced by a [`SecretKey`], tagged with the algorithm of the key.
+///
+/// Signatures serialize as their [`Algorithm`] followed by the raw bytes, so
+/// that the encoding does not depend on which cargo features are enabled.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Signature {
+    /// An Ed25519 signature.
+    #[cfg(feature = "ed25519")]
+    Ed25519(Vec<u8>),
+    /// An RSA PKCS#1 v1.5 signature.
+    #[cfg(feature = "rsa")]
+    Rsa(Vec<u8>),
+    /// A Secp256k1 ECDSA signature.
+    #[cfg(feature = "secp256k1")]
+    Secp256k1(Vec<u8>),
+    /// A BLS12-381 signature.
+    #[cfg(feature = "bls")]
+    Bls(Vec<u8>),
+    /// A P-256 ECDSA signature.
+    #[cfg(feature = "p256")]
+    P256(Vec<u8>),
+}
+
+impl Signature {
+    /// Tag the raw bytes of a signature with `algorithm`.
+    ///
+    /// Fails if the feature of `algorithm` is disabled.
+    pub fn from_bytes(algorithm: Algorithm, sig: Vec<u8>) -> Result<Signature, Error> {
+        match algorithm {
+            #[cfg(feature = "ed25519")]
+            Algorithm::ED25519 => Ok(Signature::Ed25519(sig)),
+            #[cfg(feature = "rsa")]
+            Algorithm::RSA => Ok(Signature::Rsa(sig)),
+            #[cfg(feature = "secp256k1")]
+            Algorithm::SECP256K1 => Ok(Signature::Secp256k1(sig)),
+            #[cfg(feature = "bls")]
+            Algorithm::BLS => Ok(Signature::Bls(sig)),
+            #[cfg(feature = "p256")]
+            Algorithm::P256 => Ok(Signature::P256(sig)),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::Unsupported { algorithm, operation: "Decoding" }),
+        }
+    }
+
+    /// The algorithm of the key that produced this signature.
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            #[cfg(feature = "ed25519")]
+            Signature::Ed25519(_) => Algorithm::ED25519,
+            #[cfg(feature = "rsa")]
+            Signature::Rsa(_) => Algorithm::RSA,
+            #[cfg(feature = "secp256k1")]
+            Signature::Secp256k1(_) => Algorithm::SECP256K1,
+            #[cfg(feature = "bls")]
+            Signature::Bls(_) => Algorithm::BLS,
+            #[cfg(feature = "p256")]
+            Signature::P256(_) => Algorithm::P256,
+        }
+    }
+
+    /// The raw bytes of the signature.
+    pub fn as_bytes(&self) -> &[u8] {
+        use Signature::*;
+        match self {
+            #[cfg(feature = "ed25519")]
+            Ed25519(sig) => sig,
+            #[cfg(feature = "rsa")]
+            Rsa(sig) => sig,
+            #[cfg(feature = "secp256k1")]
+            Secp256k1(sig) => sig,
+            #[cfg(feature = "bls")]
+            Bls(sig) => sig,
+            #[cfg(feature = "p256")]
+            P256(sig) => sig,
+        }
+    }
+}
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] { self.as_bytes() }
+}
+
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.algorithm(), self.as_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (algorithm, sig) = <(Algorithm, Vec<u8>)>::deserialize(deserializer)?;
+        Signature::from_bytes(algorithm, sig).map_err(serde::de::Error::custom)
     }
 }
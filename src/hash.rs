@@ -3,26 +3,82 @@ use std::fmt::{self, Debug, Display};
 use std::io::Write;
 use std::marker::PhantomData;
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+#[cfg(feature = "blake3")]
+pub use blake3::Hasher as Blake3;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
+#[cfg(feature = "sha3")]
+pub use sha3::{Keccak256, Sha3_256};
+pub use sha2::{Sha256, Sha512_256};
+
+use crate::Domain;
+
+/// A hash function with a 32-byte digest, usable as the backend of `Hash<T, H>`.
+///
+/// SHA-256 and SHA-512/256 are always available, SHA3-256 and Keccak-256 need
+/// the `sha3` feature and BLAKE3 the `blake3` feature.
+pub trait HashFunction: Default {
+    /// Feed more data into the hash function.
+    fn update(&mut self, data: &[u8]);
+
+    /// Consume the state and return the digest.
+    fn finalize(self) -> [u8; HASH_SIZE];
+
+    /// Hash a single buffer.
+    #[inline]
+    fn digest(data: &[u8]) -> [u8; HASH_SIZE] {
+        let mut hasher = Self::default();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+macro_rules! impl_hash_function_for_digest {
+    ($digest:path => $($ty:ty),+) => {
+        $(
+            impl HashFunction for $ty {
+                #[inline]
+                fn update(&mut self, data: &[u8]) { <$ty as $digest>::update(self, data) }
+
+                #[inline]
+                fn finalize(self) -> [u8; HASH_SIZE] { <$ty as $digest>::finalize(self).into() }
+            }
+        )+
+    };
+}
+
+impl_hash_function_for_digest!(sha2::Digest => Sha256, Sha512_256);
+
+#[cfg(feature = "sha3")]
+impl_hash_function_for_digest!(sha3::Digest => Sha3_256, Keccak256);
+
+#[cfg(feature = "blake3")]
+impl HashFunction for Blake3 {
+    #[inline]
+    fn update(&mut self, data: &[u8]) { Blake3::update(self, data); }
+
+    #[inline]
+    fn finalize(self) -> [u8; HASH_SIZE] { Blake3::finalize(&self).into() }
+}
 
 /// A buffered writer that accumulates bytes on the stack before flushing
-/// to SHA256 in large chunks. This avoids both heap allocation (unlike
-/// bincode::serialize → Vec) and per-field update overhead (unlike
+/// to the hash function in large chunks. This avoids both heap allocation
+/// (unlike bincode::serialize → Vec) and per-field update overhead (unlike
 /// unbuffered writes to Sha256::update).
 const HASH_BUF_SIZE: usize = 512;
 
-struct BufHashWriter {
-    hasher: Sha256,
+struct BufHashWriter<H> {
+    hasher: H,
     buf: [u8; HASH_BUF_SIZE],
     pos: usize,
 }
 
-impl BufHashWriter {
+impl<H: HashFunction> BufHashWriter<H> {
     #[inline]
     fn new() -> Self {
         Self {
-            hasher: Sha256::new(),
+            hasher: H::default(),
             buf: [0u8; HASH_BUF_SIZE],
             pos: 0,
         }
@@ -39,11 +95,11 @@ impl BufHashWriter {
     #[inline]
     fn finalize(mut self) -> [u8; 32] {
         self.flush_buf();
-        self.hasher.finalize().into()
+        self.hasher.finalize()
     }
 }
 
-impl Write for BufHashWriter {
+impl<H: HashFunction> Write for BufHashWriter<H> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         let len = buf.len();
@@ -73,96 +129,140 @@ impl Write for BufHashWriter {
 
 const HASH_SIZE: usize = 32;
 
-#[derive(Default, Deserialize, Serialize)]
-pub struct Hash<T> {
+/// A typed 32-byte digest of a `T`, computed with the hash function `H`.
+#[derive(Deserialize, Serialize)]
+#[serde(bound = "")]
+pub struct Hash<T, H = Sha256> {
     inner: [u8; HASH_SIZE],
     _x: PhantomData<T>,
+    // Not serialized, so that the encoding is the same as before `H` was added.
+    #[serde(skip)]
+    _h: PhantomData<H>,
+}
+
+impl<T, H> Default for Hash<T, H> {
+    fn default() -> Self { Self::EMPTY_HASH }
 }
 
 /// Hash<T> is cloneable even if T is not cloneable
-impl<T> Clone for Hash<T> {
-    fn clone(&self) -> Self { Self { inner: self.inner, _x: PhantomData } }
+impl<T, H> Clone for Hash<T, H> {
+    fn clone(&self) -> Self { Self::from_inner(self.inner) }
 }
 
-impl<T> std::hash::Hash for Hash<T> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.inner.hash(state); }
+impl<T, H> std::hash::Hash for Hash<T, H> {
+    fn hash<S: std::hash::Hasher>(&self, state: &mut S) { self.inner.hash(state); }
 }
 
-impl<T> PartialEq for Hash<T> {
+impl<T, H> PartialEq for Hash<T, H> {
     fn eq(&self, other: &Self) -> bool { self.inner == other.inner }
 }
 
-impl<T> Eq for Hash<T> {}
+impl<T, H> Eq for Hash<T, H> {}
 
-impl<T> PartialOrd for Hash<T> {
+impl<T, H> PartialOrd for Hash<T, H> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.inner.partial_cmp(&other.inner)
+        Some(self.cmp(other))
     }
 }
 
-impl<T> Ord for Hash<T> {
+impl<T, H> Ord for Hash<T, H> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.inner.cmp(&other.inner) }
 }
 
-impl<T> Hash<T> {
-    pub const EMPTY_HASH: Hash<T> = Hash::<T> { 
+impl<T, H> Hash<T, H> {
+    pub const EMPTY_HASH: Hash<T, H> = Hash::<T, H> { 
         inner: [0; HASH_SIZE], 
         _x: PhantomData,
+        _h: PhantomData,
     };
 
-    /// Creates a hash from serialized data
     #[inline]
-    pub fn do_hash(serialized: &[u8]) -> Self {
-        let hash = Sha256::digest(serialized);
-        Self {
-            inner: hash.into(),
-            _x: PhantomData,
-        }
+    const fn from_inner(inner: [u8; HASH_SIZE]) -> Self {
+        Self { inner, _x: PhantomData, _h: PhantomData }
     }
 
     /// Creates a hash vector
     pub fn to_vec(&self) -> Vec<u8> { self.inner.to_vec() }
 }
 
-impl<T> AsRef<[u8]> for Hash<T> {
+impl<T, H> Hash<T, H>
+where
+    H: HashFunction,
+{
+    /// Creates a hash from serialized data
+    #[inline]
+    pub fn do_hash(serialized: &[u8]) -> Self { Self::from_inner(H::digest(serialized)) }
+
+    /// Creates a hash of the concatenation of `parts`, without copying them
+    /// into a single buffer first.
+    #[inline]
+    pub(crate) fn do_hash_parts(parts: &[&[u8]]) -> Self {
+        let mut hasher = H::default();
+        for part in parts {
+            hasher.update(part);
+        }
+        Self::from_inner(hasher.finalize())
+    }
+
+    /// Returns the hash of the `prefix` parts followed by the bincode serialized object.
+    #[inline]
+    pub(crate) fn ser_and_hash_with_prefix<U>(prefix: &[&[u8]], data: &U) -> Self
+    where
+        U: Serialize + ?Sized,
+    {
+        let mut writer = BufHashWriter::<H>::new();
+        for part in prefix {
+            writer.write_all(part).expect("Writing to a hasher cannot fail");
+        }
+        bincode::serialize_into(&mut writer, data).expect("Serialization error");
+        Self::from_inner(writer.finalize())
+    }
+}
+
+impl<T, H> AsRef<[u8]> for Hash<T, H> {
     #[inline]
     fn as_ref(&self) -> &[u8] { &self.inner }
 }
 
-impl<T> TryFrom<&[u8]> for Hash<T> {
+impl<T, H> TryFrom<&[u8]> for Hash<T, H> {
     type Error = TryFromSliceError;
     fn try_from(item: &[u8]) -> Result<Self, Self::Error> {
-        Ok(Self { inner: item.try_into()?, _x: PhantomData })
+        Ok(Self::from_inner(item.try_into()?))
     }
 }
 
-impl<T> Hash<T>
+impl<T, H> Hash<T, H>
 where
     T: Serialize,
+    H: HashFunction,
 {
     /// Returns the hash of the bincode serialized object.
     /// Uses a stack-buffered writer to avoid heap allocation while
-    /// still giving SHA256 large contiguous chunks to process.
+    /// still giving the hash function large contiguous chunks to process.
     #[inline]
     pub fn ser_and_hash(data: &T) -> Self {
-        let mut writer = BufHashWriter::new();
+        let mut writer = BufHashWriter::<H>::new();
         bincode::serialize_into(&mut writer, data).expect("Serialization error");
-        Self {
-            inner: writer.finalize(),
-            _x: PhantomData,
-        }
+        Self::from_inner(writer.finalize())
+    }
+
+    /// Returns the hash of the bincode serialized object in `domain`, i.e.
+    /// prefixed with the length-delimited domain tag.
+    #[inline]
+    pub fn ser_and_hash_in(domain: &Domain, data: &T) -> Self {
+        Self::ser_and_hash_with_prefix(&domain.prefix(), data)
     }
 }
 
-impl<T> Display for Hash<T> {
+impl<T, H> Display for Hash<T, H> {
     /// The display implementation intentionally outputs a shorter hash for easier reading
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", base64::encode(&self.inner).get(0..8).unwrap())
+        write!(f, "{}", BASE64.encode(self.inner).get(0..8).unwrap())
     }
 }
 
-impl<T> Debug for Hash<T> {
+impl<T, H> Debug for Hash<T, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", base64::encode(&self.inner).get(0..HASH_SIZE).unwrap())
+        write!(f, "{}", BASE64.encode(self.inner).get(0..HASH_SIZE).unwrap())
     }
 }
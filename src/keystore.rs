@@ -0,0 +1,208 @@
+//! Password-encrypted keypairs for storage on disk.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "bls")]
+use crate::bls;
+#[cfg(feature = "ed25519")]
+use crate::ed25519;
+#[cfg(feature = "p256")]
+use crate::p256;
+#[cfg(feature = "rsa")]
+use crate::rsa;
+#[cfg(feature = "secp256k1")]
+use crate::secp256k1;
+use crate::{Algorithm, DecodingError, Error, Keypair};
+
+/// The cost parameters of the scrypt key derivation of an encrypted keypair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    /// The base-2 logarithm of the CPU/memory cost `N`.
+    pub log_n: u8,
+    /// The block size `r`.
+    pub r: u32,
+    /// The parallelization `p`.
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    /// `N = 2^17, r = 8, p = 1`, i.e. 128 MiB of memory per derivation.
+    fn default() -> Self { Self { log_n: 17, r: 8, p: 1 } }
+}
+
+impl ScryptParams {
+    /// The largest accepted `log_n`.
+    pub const MAX_LOG_N: u8 = 20;
+    /// The largest accepted product of `r` and `p`.
+    ///
+    /// Together with `MAX_LOG_N`, this bounds a derivation to 2 GiB of memory,
+    /// so that a keystore cannot make decryption exhaust the memory.
+    pub const MAX_R_P: u64 = 16;
+}
+
+const VERSION: u8 = 1;
+
+/// The authenticated, unencrypted part of an encrypted keypair.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u8,
+    algorithm: Algorithm,
+    scrypt: ScryptParams,
+    salt: [u8; 16],
+    nonce: [u8; 24],
+}
+
+#[derive(Serialize, Deserialize)]
+struct Keystore {
+    header: Header,
+    ciphertext: Vec<u8>,
+}
+
+impl Keypair {
+    /// Encrypt the keypair under `password` with the default scrypt parameters,
+    /// see [`Keypair::to_encrypted_with`].
+    pub fn to_encrypted(&self, password: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+        self.to_encrypted_with(password, ScryptParams::default())
+    }
+
+    /// Encrypt the keypair under `password`.
+    ///
+    /// The encryption key is derived from the password and a random salt with
+    /// scrypt, and seals the secret key with XChaCha20-Poly1305. The algorithm
+    /// of the keypair, the scrypt parameters, the salt and the nonce are stored
+    /// in the clear and authenticated along with the secret key.
+    ///
+    /// Fails if the scrypt parameters are invalid or exceed the bounds of
+    /// [`ScryptParams`].
+    pub fn to_encrypted_with(
+        &self,
+        password: impl AsRef<[u8]>,
+        params: ScryptParams,
+    ) -> Result<Vec<u8>, Error> {
+        let mut header = Header {
+            version: VERSION,
+            algorithm: self.algorithm(),
+            scrypt: params,
+            salt: [0; 16],
+            nonce: [0; 24],
+        };
+        rand::thread_rng().fill_bytes(&mut header.salt);
+        rand::thread_rng().fill_bytes(&mut header.nonce);
+
+        let cipher = cipher(password.as_ref(), &header)?;
+        let aad = bincode::serialize(&header).expect("Serialization error");
+        let plaintext = encode_secret(self);
+        let payload = Payload { msg: &plaintext, aad: &aad };
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&header.nonce), payload)
+            .expect("XChaCha20-Poly1305 encryption failed.");
+        Ok(bincode::serialize(&Keystore { header, ciphertext }).expect("Serialization error"))
+    }
+
+    /// Decrypt a keypair encrypted with [`Keypair::to_encrypted`].
+    pub fn from_encrypted(bytes: &[u8], password: impl AsRef<[u8]>) -> Result<Keypair, Error> {
+        let Keystore { header, ciphertext } = bincode::deserialize(bytes)
+            .map_err(|e| DecodingError::unknown_algorithm("encrypted keypair").source(e))?;
+        let algorithm = header.algorithm;
+        if header.version != VERSION {
+            let msg = format!("unsupported keystore version {}", header.version);
+            return Err(DecodingError::new(algorithm, msg).into());
+        }
+        // Refuse keys of disabled algorithms before the costly key derivation.
+        if !is_supported(algorithm) {
+            return Err(Error::Unsupported { algorithm, operation: "Decoding" });
+        }
+
+        let cipher = cipher(password.as_ref(), &header)?;
+        let aad = bincode::serialize(&header).expect("Serialization error");
+        let payload = Payload { msg: &ciphertext, aad: &aad };
+        let mut plaintext = cipher
+            .decrypt(XNonce::from_slice(&header.nonce), payload)
+            .map(Zeroizing::new)
+            .map_err(|_| DecodingError::new(algorithm, "wrong password or corrupted keypair"))?;
+        decode_secret(algorithm, &mut plaintext)
+    }
+}
+
+/// Derive the cipher for `header` from `password`.
+///
+/// The scrypt parameters are checked against the bounds of [`ScryptParams`]
+/// before deriving the key.
+fn cipher(password: &[u8], header: &Header) -> Result<XChaCha20Poly1305, Error> {
+    let ScryptParams { log_n, r, p } = header.scrypt;
+    let invalid = |msg| DecodingError::new(header.algorithm, msg);
+    if log_n > ScryptParams::MAX_LOG_N || r as u64 * p as u64 > ScryptParams::MAX_R_P {
+        return Err(invalid("scrypt parameters exceed the bounds").into());
+    }
+    let params = scrypt::Params::new(log_n, r, p, 32)
+        .map_err(|e| invalid("invalid scrypt parameters").source(e))?;
+    let mut key = Zeroizing::new([0; 32]);
+    scrypt::scrypt(password, &header.salt, &params, &mut *key).expect("The key length is valid");
+    Ok(XChaCha20Poly1305::new(Key::from_slice(&*key)))
+}
+
+/// The encoding of the secret key of `kp`, which only depends on its algorithm:
+/// PKCS#8 for RSA and the 32-byte secret key otherwise.
+fn encode_secret(kp: &Keypair) -> Zeroizing<Vec<u8>> {
+    Zeroizing::new(match kp {
+        #[cfg(feature = "ed25519")]
+        Keypair::Ed25519(kp) => kp.secret().signing_key.to_bytes().to_vec(),
+        #[cfg(feature = "rsa")]
+        Keypair::Rsa(kp) => kp.bytes.clone(),
+        #[cfg(feature = "secp256k1")]
+        Keypair::Secp256k1(kp) => kp.to_bytes(),
+        #[cfg(feature = "bls")]
+        Keypair::Bls(kp) => kp.secret().to_bytes().to_vec(),
+        #[cfg(feature = "p256")]
+        Keypair::P256(kp) => kp.to_bytes(),
+    })
+}
+
+/// Whether keys of `algorithm` can be decoded by [`decode_secret`].
+fn is_supported(algorithm: Algorithm) -> bool {
+    match algorithm {
+        #[cfg(feature = "ed25519")]
+        Algorithm::ED25519 => true,
+        #[cfg(feature = "rsa")]
+        Algorithm::RSA => true,
+        #[cfg(feature = "secp256k1")]
+        Algorithm::SECP256K1 => true,
+        #[cfg(feature = "bls")]
+        Algorithm::BLS => true,
+        #[cfg(feature = "p256")]
+        Algorithm::P256 => true,
+        #[allow(unreachable_patterns)]
+        _ => false,
+    }
+}
+
+/// Decode a keypair of `algorithm` from its [`encode_secret`] encoding.
+fn decode_secret(algorithm: Algorithm, bytes: &mut [u8]) -> Result<Keypair, Error> {
+    match algorithm {
+        #[cfg(feature = "ed25519")]
+        Algorithm::ED25519 => {
+            let seed: &[u8; ed25519::SecretKey::SIZE] = (&*bytes)
+                .try_into()
+                .map_err(|e| DecodingError::new(algorithm, "Ed25519 secret key").source(e))?;
+            let signing_key = ed25519_dalek::SigningKey::from_bytes(seed);
+            let sk = ed25519::SecretKey { signing_key };
+            Ok(Keypair::Ed25519(Box::new(sk.into())))
+        }
+        #[cfg(feature = "rsa")]
+        Algorithm::RSA => rsa::Keypair::from_pkcs8(bytes).map(Keypair::Rsa),
+        #[cfg(feature = "secp256k1")]
+        Algorithm::SECP256K1 => secp256k1::Keypair::from_bytes(bytes).map(Keypair::Secp256k1),
+        #[cfg(feature = "bls")]
+        Algorithm::BLS => {
+            bls::SecretKey::from_bytes(&*bytes).map(|sk| Keypair::Bls(bls::Keypair::from(sk)))
+        }
+        #[cfg(feature = "p256")]
+        Algorithm::P256 => p256::Keypair::from_bytes(bytes).map(Keypair::P256),
+        #[allow(unreachable_patterns)]
+        _ => Err(Error::Unsupported { algorithm, operation: "Decoding" }),
+    }
+}
@@ -1,4 +1,7 @@
+pub mod aead;
 pub mod hash;
+pub mod merkle;
+pub mod sharing;
 
 mod crypto;
 pub use crypto::*;
@@ -9,6 +12,26 @@ pub use gen::*;
 mod error;
 pub use error::*;
 
+mod domain;
+pub use domain::*;
+
+mod signed;
+pub use signed::*;
+
+mod keystore;
+pub use keystore::*;
+
+mod multicodec;
+
+mod derivation;
+pub use derivation::*;
+
+#[cfg(any(feature = "ed25519", feature = "rsa", feature = "secp256k1", feature = "p256"))]
+mod pem;
+
+#[cfg(test)]
+mod tests;
+
 // Supports the following cryptographic schemes
 // Might add more in the future
 #[cfg(feature = "ed25519")]
@@ -17,6 +40,17 @@ pub mod ed25519;
 #[cfg(feature = "secp256k1")]
 pub mod secp256k1;
 
-// Not implemented
+#[cfg(feature = "p256")]
+pub mod p256;
+
 #[cfg(feature = "rsa")]
 pub mod rsa;
+
+#[cfg(feature = "bls")]
+pub mod bls;
+
+#[cfg(feature = "threshold")]
+pub mod threshold;
+
+#[cfg(feature = "x25519")]
+pub mod x25519;
@@ -0,0 +1,42 @@
+//! Merkle trees over serializable leaves.
+//!
+//! Leaves and internal nodes are hashed in two reserved [`Domain`]s, as
+//! `H(leaf domain || bincode(leaf))` and `H(node domain || left || right)`, so
+//! that a leaf can never be passed off as an internal node (and vice versa),
+//! nor collide with a hash in any other domain. The root of an empty tree is
+//! the hash of the empty string in a third reserved domain. When a level has
+//! an odd number of nodes, the last node is promoted to the next level
+//! unchanged instead of being duplicated.
+
+mod tree;
+pub use tree::*;
+
+mod proof;
+pub use proof::*;
+
+#[cfg(test)]
+mod tests;
+
+use crate::hash::Hash;
+use crate::Domain;
+
+const LEAF_DOMAIN: Domain = Domain::reserved("libcrypto/merkle/leaf");
+const NODE_DOMAIN: Domain = Domain::reserved("libcrypto/merkle/node");
+const EMPTY_DOMAIN: Domain = Domain::reserved("libcrypto/merkle/empty");
+
+/// The root of a Merkle tree with leaves of type `T`.
+pub type Root<T> = Hash<MerkleTree<T>>;
+
+fn hash_leaf<T: serde::Serialize>(leaf: &T) -> Root<T> {
+    Hash::ser_and_hash_with_prefix(&LEAF_DOMAIN.prefix(), leaf)
+}
+
+fn hash_empty<T>() -> Root<T> {
+    let [len, tag] = EMPTY_DOMAIN.prefix();
+    Hash::do_hash_parts(&[len, tag])
+}
+
+fn hash_node<T>(left: &Root<T>, right: &Root<T>) -> Root<T> {
+    let [len, tag] = NODE_DOMAIN.prefix();
+    Hash::do_hash_parts(&[len, tag, left.as_ref(), right.as_ref()])
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use super::{hash_leaf, hash_node, Root};
+
+/// A proof that a leaf is included at a given position in a Merkle tree.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MerkleProof<T> {
+    index: usize,
+    leaves: usize,
+    siblings: Vec<Root<T>>,
+}
+
+/// MerkleProof<T> is cloneable even if T is not cloneable
+impl<T> Clone for MerkleProof<T> {
+    fn clone(&self) -> Self {
+        Self { index: self.index, leaves: self.leaves, siblings: self.siblings.clone() }
+    }
+}
+
+impl<T> PartialEq for MerkleProof<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.leaves == other.leaves && self.siblings == other.siblings
+    }
+}
+
+impl<T> Eq for MerkleProof<T> {}
+
+impl<T> MerkleProof<T> {
+    pub(crate) fn new(index: usize, leaves: usize, siblings: Vec<Root<T>>) -> Self {
+        Self { index, leaves, siblings }
+    }
+
+    /// The position of the leaf in the tree.
+    pub fn index(&self) -> usize { self.index }
+
+    /// The number of leaves in the tree.
+    pub fn leaves(&self) -> usize { self.leaves }
+
+    /// The sibling hashes on the path from the leaf to the root.
+    pub fn siblings(&self) -> &[Root<T>] { &self.siblings }
+}
+
+impl<T> MerkleProof<T>
+where
+    T: Serialize,
+{
+    /// Compute the root of the tree implied by this proof for `leaf`.
+    ///
+    /// Returns `None` if the proof is malformed for the claimed tree size.
+    pub fn compute_root(&self, leaf: &T) -> Option<Root<T>> {
+        if self.index >= self.leaves {
+            return None;
+        }
+        let mut siblings = self.siblings.iter();
+        let mut hash = hash_leaf(leaf);
+        let (mut i, mut width) = (self.index, self.leaves);
+        while width > 1 {
+            if i % 2 == 1 {
+                hash = hash_node(siblings.next()?, &hash);
+            } else if i + 1 < width {
+                hash = hash_node(&hash, siblings.next()?);
+            }
+            i /= 2;
+            width = width.div_ceil(2);
+        }
+        match siblings.next() {
+            Some(_) => None,
+            None => Some(hash),
+        }
+    }
+
+    /// Verify that `leaf` is included at `self.index()` in the tree with the given root.
+    pub fn verify(&self, leaf: &T, root: &Root<T>) -> bool {
+        self.compute_root(leaf).as_ref() == Some(root)
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::hash::Hash;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Tx {
+    from: u64,
+    amount: u64,
+}
+
+fn txs(n: u64) -> Vec<Tx> { (0..n).map(|i| Tx { from: i, amount: 10 * i }).collect() }
+
+#[test]
+fn merkle_proofs_all_sizes() {
+    for n in 1..=17 {
+        let leaves = txs(n);
+        let tree = MerkleTree::new(&leaves);
+        assert_eq!(tree.len(), n as usize);
+        let root = tree.root();
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(i).unwrap();
+            assert_eq!(proof.index(), i);
+            assert!(proof.verify(leaf, &root));
+            // The same proof does not hold for another leaf.
+            assert!(!proof.verify(&leaves[(i + 1) % leaves.len()], &root) || n == 1);
+        }
+        assert!(tree.proof(n as usize).is_none());
+    }
+}
+
+#[test]
+fn merkle_root_depends_on_leaves() {
+    let mut leaves = txs(5);
+    let root = MerkleTree::new(&leaves).root();
+    assert_eq!(root, MerkleTree::new(&leaves).root());
+    leaves[3].amount += 1;
+    assert_ne!(root, MerkleTree::new(&leaves).root());
+    // Duplicating the last leaf changes the root, unlike Bitcoin-style trees.
+    let mut dup = txs(3);
+    dup.push(Tx { from: 2, amount: 20 });
+    assert_ne!(MerkleTree::new(&txs(3)).root(), MerkleTree::new(&dup).root());
+}
+
+#[test]
+fn merkle_domain_separation() {
+    let leaves = txs(2);
+    let tree = MerkleTree::new(&leaves);
+    // The root of a single-leaf tree is the leaf hash, which differs from the plain hash.
+    let single = MerkleTree::new(&leaves[..1]);
+    assert_ne!(single.root().as_ref(), Hash::<Tx>::ser_and_hash(&leaves[0]).as_ref());
+    // Internal nodes are prefixed too.
+    let (l0, l1) = (single.root(), MerkleTree::new(&leaves[1..]).root());
+    let node = NODE_DOMAIN.tagged(&[l0.as_ref(), l1.as_ref()].concat());
+    assert_eq!(tree.root(), Hash::do_hash(&node));
+    assert_ne!(tree.root(), Hash::do_hash_parts(&[l0.as_ref(), l1.as_ref()]));
+    let leaf = LEAF_DOMAIN.tagged(&bincode::serialize(&leaves[0]).unwrap());
+    assert_eq!(single.root(), Hash::do_hash(&leaf));
+}
+
+#[test]
+fn merkle_proof_tampering() {
+    let leaves = txs(6);
+    let tree = MerkleTree::new(&leaves);
+    let root = tree.root();
+    let proof = tree.proof(4).unwrap();
+
+    let bytes = bincode::serialize(&proof).unwrap();
+    let decoded: MerkleProof<Tx> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, proof);
+    assert!(decoded.verify(&leaves[4], &root));
+
+    let wrong_index = MerkleProof::new(5, proof.leaves(), proof.siblings().to_vec());
+    assert!(!wrong_index.verify(&leaves[4], &root));
+    let wrong_size = MerkleProof::new(4, 8, proof.siblings().to_vec());
+    assert!(!wrong_size.verify(&leaves[4], &root));
+    let mut extra = proof.siblings().to_vec();
+    extra.push(root.clone());
+    assert!(!MerkleProof::new(4, 6, extra).verify(&leaves[4], &root));
+    assert!(!MerkleProof::new(6, 6, proof.siblings().to_vec()).verify(&leaves[4], &root));
+}
+
+#[test]
+fn merkle_empty_tree() {
+    let tree = MerkleTree::<Tx>::new(&[]);
+    assert!(tree.is_empty());
+    assert!(tree.proof(0).is_none());
+    assert_eq!(tree.root(), Hash::do_hash(&EMPTY_DOMAIN.tagged(&[])));
+    assert_ne!(tree.root(), Hash::do_hash(&[]));
+}
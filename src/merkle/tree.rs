@@ -0,0 +1,86 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::Serialize;
+
+use super::{hash_empty, hash_leaf, hash_node, MerkleProof, Root};
+
+/// A Merkle tree over leaves of type `T`.
+///
+/// Only the hashes are kept, the leaves themselves are not stored.
+pub struct MerkleTree<T> {
+    /// All levels of the tree, from the leaf hashes up to the root.
+    levels: Vec<Vec<Root<T>>>,
+    _x: PhantomData<T>,
+}
+
+/// MerkleTree<T> is cloneable even if T is not cloneable
+impl<T> Clone for MerkleTree<T> {
+    fn clone(&self) -> Self { Self { levels: self.levels.clone(), _x: PhantomData } }
+}
+
+impl<T> fmt::Debug for MerkleTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleTree")
+            .field("leaves", &self.len())
+            .field("root", &self.root())
+            .finish()
+    }
+}
+
+impl<T> MerkleTree<T>
+where
+    T: Serialize,
+{
+    /// Build a tree from a list of leaves.
+    pub fn new(leaves: &[T]) -> Self {
+        let mut levels = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels.last().unwrap();
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels, _x: PhantomData }
+    }
+}
+
+impl<T> MerkleTree<T> {
+    /// The number of leaves in the tree.
+    pub fn len(&self) -> usize { self.levels[0].len() }
+
+    /// Whether the tree has no leaves.
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// The root of the tree. The root of an empty tree is the hash of the empty
+    /// string in its own domain.
+    pub fn root(&self) -> Root<T> {
+        match self.levels.last().unwrap().first() {
+            Some(root) => root.clone(),
+            None => hash_empty(),
+        }
+    }
+
+    /// An inclusion proof for the leaf at `index`, or `None` if it is out of range.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof<T>> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut siblings = Vec::with_capacity(self.levels.len() - 1);
+        let mut i = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = i ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling].clone());
+            }
+            i /= 2;
+        }
+        Some(MerkleProof::new(index, self.len(), siblings))
+    }
+}
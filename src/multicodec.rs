@@ -0,0 +1,156 @@
+//! Self-describing encoding of public keys, as used for `PublicKey`'s serde
+//! implementation.
+//!
+//! A key is encoded as the [multicodec] code of its algorithm, written as an
+//! unsigned varint, followed by the key itself: the compressed point for
+//! Ed25519, Secp256k1, P-256 and BLS12-381 (G1) keys, and the DER-encoded
+//! PKCS#1 RSAPublicKey for RSA keys. Unlike the enum discriminants bincode
+//! writes for a derived implementation, the codes do not depend on which cargo
+//! features are enabled, and keys of a disabled algorithm are reported as
+//! unsupported instead of being read as some other kind of key.
+//!
+//! [multicodec]: https://github.com/multiformats/multicodec/blob/master/table.csv
+
+use std::fmt;
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "bls")]
+use crate::bls;
+#[cfg(feature = "ed25519")]
+use crate::ed25519;
+#[cfg(feature = "p256")]
+use crate::p256;
+#[cfg(feature = "rsa")]
+use crate::rsa;
+#[cfg(feature = "secp256k1")]
+use crate::secp256k1;
+use crate::{Algorithm, DecodingError, Error, PublicKey};
+
+/// The multicodec code of each algorithm's public keys.
+const CODES: [(Algorithm, u64); 5] = [
+    (Algorithm::ED25519, 0xed),
+    (Algorithm::SECP256K1, 0xe7),
+    (Algorithm::BLS, 0xea),
+    (Algorithm::P256, 0x1200),
+    (Algorithm::RSA, 0x1205),
+];
+
+impl PublicKey {
+    /// Encode the public key, prefixed with the multicodec code of its algorithm.
+    pub fn to_multicodec(&self) -> Vec<u8> {
+        let algorithm = self.algorithm();
+        let (_, code) = CODES.iter().find(|(a, _)| *a == algorithm).expect("all algorithms");
+        let mut bytes = Vec::new();
+        write_varint(*code, &mut bytes);
+        match self {
+            #[cfg(feature = "ed25519")]
+            PublicKey::Ed25519(pk) => bytes.extend_from_slice(pk.0.as_bytes()),
+            #[cfg(feature = "rsa")]
+            PublicKey::Rsa(pk) => bytes.extend_from_slice(&pk.encode_pkcs1()),
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(pk) => bytes.extend_from_slice(&pk.encode()),
+            #[cfg(feature = "bls")]
+            PublicKey::Bls(pk) => bytes.extend_from_slice(&pk.encode()),
+            #[cfg(feature = "p256")]
+            PublicKey::P256(pk) => bytes.extend_from_slice(&pk.encode()),
+        }
+        bytes
+    }
+
+    /// Decode a public key written by `to_multicodec`.
+    ///
+    /// Keys of an algorithm whose cargo feature is disabled are rejected with
+    /// [`Error::Unsupported`].
+    pub fn from_multicodec(bytes: &[u8]) -> Result<PublicKey, Error> {
+        let (code, key) = read_varint(bytes)
+            .ok_or_else(|| DecodingError::unknown_algorithm("Invalid multicodec prefix"))?;
+        let algorithm = CODES.iter().find(|(_, c)| *c == code).map(|(a, _)| *a).ok_or_else(|| {
+            DecodingError::unknown_algorithm(format!("Unknown multicodec 0x{code:x}"))
+        })?;
+        match algorithm {
+            #[cfg(feature = "ed25519")]
+            Algorithm::ED25519 => {
+                let vk = ed25519_dalek::VerifyingKey::try_from(key).map_err(|e| {
+                    DecodingError::new(algorithm, "Ed25519 public key").source(e)
+                })?;
+                Ok(PublicKey::Ed25519(ed25519::PublicKey(vk)))
+            }
+            #[cfg(feature = "rsa")]
+            Algorithm::RSA => {
+                openssl::rsa::Rsa::public_key_from_der_pkcs1(key)
+                    .map_err(|e| DecodingError::new(algorithm, "PKCS#1 RSAPublicKey").source(e))?;
+                Ok(PublicKey::Rsa(rsa::PublicKey(key.to_vec())))
+            }
+            #[cfg(feature = "secp256k1")]
+            Algorithm::SECP256K1 => secp256k1::PublicKey::decode(key).map(PublicKey::Secp256k1),
+            #[cfg(feature = "bls")]
+            Algorithm::BLS => bls::PublicKey::decode(key).map(PublicKey::Bls),
+            #[cfg(feature = "p256")]
+            Algorithm::P256 => p256::PublicKey::decode(key).map(PublicKey::P256),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::Unsupported { algorithm, operation: "Decoding" }),
+        }
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_multicodec())
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(PublicKeyVisitor)
+    }
+}
+
+struct PublicKeyVisitor;
+
+impl<'de> Visitor<'de> for PublicKeyVisitor {
+    type Value = PublicKey;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a multicodec-prefixed public key")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<PublicKey, E> {
+        PublicKey::from_multicodec(v).map_err(E::custom)
+    }
+
+    // Formats without a native byte string, like JSON, write bytes as a sequence.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PublicKey, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+fn write_varint(mut n: u64, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Read an unsigned varint, returning it and the remaining bytes. Only the
+/// minimal encoding of a number is accepted, so that every key has exactly one
+/// encoding.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut n = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(9) {
+        n |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            if byte == 0 && i > 0 {
+                return None;
+            }
+            return Some((n, &bytes[i + 1..]));
+        }
+    }
+    None
+}
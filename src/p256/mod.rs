@@ -0,0 +1,13 @@
+//! NIST P-256 (secp256r1) keys with ECDSA signatures.
+
+mod keypair;
+pub use keypair::*;
+
+mod secret;
+pub use secret::*;
+
+mod public;
+pub use public::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,86 @@
+use std::{fmt, hash::Hash};
+
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::{Algorithm, DecodingError, Error, VerificationError};
+
+/// A P-256 public key.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct PublicKey(pub(crate) VerifyingKey);
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PublicKey(compressed): ")?;
+        for byte in &self.encode() {
+            write!(f, "{:x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derived_hash_with_manual_eq)]
+impl Hash for PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.encode().hash(state); }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.encode().cmp(&other.encode()) }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.encode())
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        PublicKey::decode(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+impl PublicKey {
+    /// Verify the DER-encoded ECDSA signature on a message using the public key.
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool { self.try_verify(msg, sig).is_ok() }
+
+    /// Verify the signature on a message like `verify`, but report why it is invalid.
+    pub fn try_verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
+        let sig = Signature::from_der(sig)
+            .map_err(|_| VerificationError::Malformed("P-256 DER signature"))?;
+        self.0.verify(msg, &sig).map_err(|_| VerificationError::Invalid("P-256"))
+    }
+
+    /// Encode the public key in compressed SEC1 form, i.e. with one coordinate
+    /// represented by a single bit.
+    pub fn encode(&self) -> [u8; 33] {
+        let point = self.0.to_encoded_point(true);
+        point.as_bytes().try_into().expect("compressed P-256 point is 33 bytes")
+    }
+
+    /// Encode the public key in uncompressed SEC1 form.
+    pub fn encode_uncompressed(&self) -> [u8; 65] {
+        let point = self.0.to_encoded_point(false);
+        point.as_bytes().try_into().expect("uncompressed P-256 point is 65 bytes")
+    }
+
+    /// Decode a public key from a byte slice in either the compressed or the
+    /// uncompressed SEC1 form.
+    pub fn decode(k: &[u8]) -> Result<PublicKey, Error> {
+        let pk = VerifyingKey::from_sec1_bytes(k)
+            .map_err(|e| DecodingError::new(Algorithm::P256, "SEC1 public key").source(e))?;
+        Ok(PublicKey(pk))
+    }
+}
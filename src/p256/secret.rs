@@ -0,0 +1,120 @@
+use std::fmt;
+
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::PublicKey;
+use crate::{Algorithm, DecodingError, Error, SigningError};
+
+/// A P-256 secret key.
+///
+/// The key is wiped from memory when dropped.
+///
+/// Secret keys are compared in constant time, and are deliberately neither
+/// `Hash` nor `Ord`.
+#[derive(Clone)]
+pub struct SecretKey(pub(crate) SigningKey);
+
+/// The inner signing key wipes itself on drop.
+impl ZeroizeOnDrop for SecretKey {}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SecretKey") }
+}
+
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice { self.0.ct_eq(&other.0) }
+}
+
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
+}
+
+impl Eq for SecretKey {}
+
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+struct Serialized([u8; 32]);
+
+impl Serialize for SecretKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Serialized(self.to_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = Serialized::deserialize(deserializer)?;
+        SecretKey::from_bytes(data.0).map_err(serde::de::Error::custom)
+    }
+}
+
+impl SecretKey {
+    /// Generate a new P-256 secret key.
+    pub fn generate() -> SecretKey { Self::generate_with_rng(&mut rand::thread_rng()) }
+
+    /// Generate a new P-256 secret key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SecretKey {
+        SecretKey(SigningKey::random(rng))
+    }
+
+    /// Deterministically derive a secret key from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> SecretKey {
+        Self::generate_with_rng(&mut crate::seeded_rng(seed))
+    }
+
+    /// Create a secret key from its 32 big-endian bytes. If the bytes do not
+    /// constitute a valid P-256 secret key, an error is returned.
+    pub fn from_bytes(sk: impl AsRef<[u8]>) -> Result<SecretKey, Error> {
+        let key = SigningKey::from_slice(sk.as_ref())
+            .map_err(|e| DecodingError::new(Algorithm::P256, "secret key").source(e))?;
+        Ok(SecretKey(key))
+    }
+
+    /// Decode a DER-encoded P-256 secret key in an ECPrivateKey
+    /// structure as defined in [RFC5915].
+    ///
+    /// [RFC5915]: https://tools.ietf.org/html/rfc5915
+    pub fn from_der(der: impl AsRef<[u8]>) -> Result<SecretKey, Error> {
+        let sk = p256::SecretKey::from_sec1_der(der.as_ref())
+            .map_err(|e| DecodingError::new(Algorithm::P256, "ECPrivateKey").source(e))?;
+        Ok(SecretKey(SigningKey::from(sk)))
+    }
+
+    /// Encode the secret key as a DER ECPrivateKey structure as defined in [RFC5915].
+    ///
+    /// [RFC5915]: https://tools.ietf.org/html/rfc5915
+    pub fn to_der(&self) -> Vec<u8> {
+        let sk = p256::SecretKey::from(&self.0);
+        sk.to_sec1_der().expect("P-256 ECPrivateKey encoding failed.").to_vec()
+    }
+
+    /// Returns the raw bytes of the secret key.
+    pub fn to_bytes(&self) -> [u8; 32] { self.0.to_bytes().into() }
+
+    /// Get the public key corresponding to this secret key.
+    pub fn public(&self) -> PublicKey { PublicKey(*self.0.verifying_key()) }
+
+    /// Sign a message with this secret key, producing a DER-encoded ECDSA
+    /// signature over the SHA-256 digest of the message, with deterministic
+    /// nonces as defined in [RFC6979].
+    ///
+    /// [RFC6979]: https://tools.ietf.org/html/rfc6979
+    pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        let sig: Signature = self
+            .0
+            .try_sign(msg)
+            .map_err(|e| SigningError::new(Algorithm::P256, "ECDSA").source(e))?;
+        Ok(sig.to_der().as_bytes().to_vec())
+    }
+}
@@ -0,0 +1,59 @@
+use openssl::ec::{EcGroup, EcKey};
+use openssl::nid::Nid;
+
+use super::*;
+
+#[test]
+fn p256_sign_verify() {
+    let kp = Keypair::generate();
+    let msg = "hello world".as_bytes();
+    let sig = kp.secret().sign(msg).unwrap();
+    assert!(kp.public().verify(msg, &sig));
+    assert!(!kp.public().verify("h3ll0 w0rld".as_bytes(), &sig));
+    assert!(!Keypair::generate().public().verify(msg, &sig));
+    assert!(!kp.public().verify(msg, &sig[1..]));
+}
+
+#[test]
+fn p256_sec1_encoding() {
+    let kp = Keypair::generate();
+    let compressed = kp.public().encode();
+    let uncompressed = kp.public().encode_uncompressed();
+    assert!(compressed[0] == 2 || compressed[0] == 3);
+    assert_eq!(uncompressed[0], 4);
+    assert_eq!(compressed[1..], uncompressed[1..33]);
+    assert_eq!(&PublicKey::decode(&compressed).unwrap(), kp.public());
+    assert_eq!(&PublicKey::decode(&uncompressed).unwrap(), kp.public());
+    assert!(PublicKey::decode(&compressed[1..]).is_err());
+}
+
+#[test]
+fn p256_codec() {
+    let kp = Keypair::generate();
+    let mut bytes = kp.to_bytes();
+    assert_eq!(Keypair::from_bytes(&mut bytes).unwrap(), kp);
+    let decoded: Keypair = bincode::deserialize(&bincode::serialize(&kp).unwrap()).unwrap();
+    assert_eq!(decoded, kp);
+    assert_eq!(&SecretKey::from_der(kp.secret().to_der()).unwrap(), kp.secret());
+}
+
+#[test]
+fn p256_openssl_interop() {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+    let key = EcKey::generate(&group).unwrap();
+    let mut der = key.private_key_to_der().unwrap();
+    let kp = crate::Keypair::p256_from_der(&mut der).unwrap();
+
+    let mut ctx = openssl::bn::BigNumContext::new().unwrap();
+    let form = openssl::ec::PointConversionForm::COMPRESSED;
+    let openssl_pk = key.public_key().to_bytes(&group, form, &mut ctx).unwrap();
+    match kp.public() {
+        crate::PublicKey::P256(pk) => assert_eq!(pk.encode().to_vec(), openssl_pk),
+        #[allow(unreachable_patterns)]
+        _ => panic!("not a P-256 key"),
+    }
+
+    let msg = "hello world".as_bytes();
+    let sig = kp.private().sign(msg).unwrap();
+    assert!(kp.public().verify(msg, &sig));
+}
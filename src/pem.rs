@@ -0,0 +1,277 @@
+//! PEM encoding of keys, as read and written by `openssl genpkey` and `openssl pkey`.
+//!
+//! Private keys are written as PKCS#8 `PRIVATE KEY` documents and public keys
+//! as X.509 SubjectPublicKeyInfo `PUBLIC KEY` documents. When decoding, the
+//! algorithm is detected from the OID of the key (and the named curve of EC
+//! keys), and the traditional `RSA PRIVATE KEY`, `EC PRIVATE KEY` and
+//! `RSA PUBLIC KEY` documents are accepted as well. Keypairs can also be
+//! encrypted with a password as PKCS#8 `ENCRYPTED PRIVATE KEY` documents.
+//! BLS12-381 keys have no standard encoding and are not supported.
+
+#[cfg(any(feature = "secp256k1", feature = "p256"))]
+use openssl::bn::{BigNum, BigNumContext};
+#[cfg(any(feature = "secp256k1", feature = "p256"))]
+use openssl::ec::{EcGroup, EcKey, EcPoint, PointConversionForm};
+use openssl::error::ErrorStack;
+use openssl::nid::Nid;
+use openssl::pkey::{HasParams, Id, PKey, PKeyRef, Private, Public};
+use openssl::symm::Cipher;
+use zeroize::Zeroizing;
+
+#[cfg(feature = "ed25519")]
+use crate::ed25519;
+#[cfg(feature = "p256")]
+use crate::p256;
+#[cfg(feature = "rsa")]
+use crate::rsa;
+#[cfg(feature = "secp256k1")]
+use crate::secp256k1;
+use crate::{Algorithm, DecodingError, Error, Keypair, PublicKey, SecretKey};
+
+impl Keypair {
+    /// Encode the keypair as a PKCS#8 `PRIVATE KEY` PEM document.
+    pub fn to_pem(&self) -> Result<Zeroizing<String>, Error> {
+        let pem = converted(self.algorithm(), private_pkey(self)?.private_key_to_pem_pkcs8())?;
+        Ok(Zeroizing::new(String::from_utf8(pem).expect("PEM is ASCII")))
+    }
+
+    /// Decode a keypair from a PKCS#8 `PRIVATE KEY`, PKCS#1 `RSA PRIVATE KEY`
+    /// or SEC1 `EC PRIVATE KEY` PEM document, detecting its algorithm.
+    pub fn from_pem(pem: &str) -> Result<Keypair, Error> {
+        // Refuse encrypted documents rather than letting OpenSSL prompt for a password.
+        let pkey = PKey::private_key_from_pem_callback(pem.as_bytes(), |_| Ok(0))
+            .map_err(|e| DecodingError::unknown_algorithm("PEM private key").source(e))?;
+        from_private_pkey(&pkey)
+    }
+
+    /// Encode the keypair as a PKCS#8 `ENCRYPTED PRIVATE KEY` PEM document,
+    /// encrypted under `password` with PBES2 and AES-256-CBC, like
+    /// `openssl genpkey -aes-256-cbc` does.
+    pub fn to_encrypted_pem(&self, password: impl AsRef<[u8]>) -> Result<String, Error> {
+        let pkey = private_pkey(self)?;
+        let pem = converted(
+            self.algorithm(),
+            pkey.private_key_to_pem_pkcs8_passphrase(Cipher::aes_256_cbc(), password.as_ref()),
+        )?;
+        Ok(String::from_utf8(pem).expect("PEM is ASCII"))
+    }
+
+    /// Decode a keypair from a PKCS#8 `ENCRYPTED PRIVATE KEY` PEM document
+    /// encrypted under `password`, detecting its algorithm.
+    pub fn from_encrypted_pem(pem: &str, password: impl AsRef<[u8]>) -> Result<Keypair, Error> {
+        let pkey = PKey::private_key_from_pem_passphrase(pem.as_bytes(), password.as_ref())
+            .map_err(|e| DecodingError::unknown_algorithm("encrypted PEM private key").source(e))?;
+        from_private_pkey(&pkey)
+    }
+}
+
+impl SecretKey {
+    /// Encode the secret key as a PKCS#8 `PRIVATE KEY` PEM document, see
+    /// [`Keypair::to_pem`].
+    pub fn to_pem(&self) -> Result<Zeroizing<String>, Error> {
+        Keypair::from(self.clone()).to_pem()
+    }
+
+    /// Decode a secret key from a PEM document, see [`Keypair::from_pem`].
+    pub fn from_pem(pem: &str) -> Result<SecretKey, Error> {
+        Keypair::from_pem(pem).map(|kp| kp.private())
+    }
+}
+
+impl PublicKey {
+    /// Encode the public key as an X.509 SubjectPublicKeyInfo `PUBLIC KEY` PEM document.
+    pub fn to_pem(&self) -> Result<String, Error> {
+        let pem = converted(self.algorithm(), public_pkey(self)?.public_key_to_pem())?;
+        Ok(String::from_utf8(pem).expect("PEM is ASCII"))
+    }
+
+    /// Decode a public key from an X.509 SubjectPublicKeyInfo `PUBLIC KEY` or a
+    /// PKCS#1 `RSA PUBLIC KEY` PEM document, detecting its algorithm.
+    pub fn from_pem(pem: &str) -> Result<PublicKey, Error> {
+        let pkey = if pem.contains("-----BEGIN RSA PUBLIC KEY-----") {
+            openssl::rsa::Rsa::public_key_from_pem_pkcs1(pem.as_bytes()).and_then(PKey::from_rsa)
+        } else {
+            PKey::public_key_from_pem(pem.as_bytes())
+        };
+        let pkey =
+            pkey.map_err(|e| DecodingError::unknown_algorithm("PEM public key").source(e))?;
+        from_public_pkey(&pkey)
+    }
+}
+
+/// The algorithm of an OpenSSL key, from its OID and the named curve of EC keys.
+fn algorithm_of<T: HasParams>(pkey: &PKeyRef<T>) -> Result<Algorithm, Error> {
+    match pkey.id() {
+        Id::ED25519 => Ok(Algorithm::ED25519),
+        Id::RSA => Ok(Algorithm::RSA),
+        Id::EC => {
+            let ec = pkey
+                .ec_key()
+                .map_err(|e| DecodingError::unknown_algorithm("EC key").source(e))?;
+            match ec.group().curve_name() {
+                Some(Nid::SECP256K1) => Ok(Algorithm::SECP256K1),
+                Some(Nid::X9_62_PRIME256V1) => Ok(Algorithm::P256),
+                _ => Err(DecodingError::unknown_algorithm("unsupported elliptic curve").into()),
+            }
+        }
+        _ => Err(DecodingError::unknown_algorithm("unsupported key type").into()),
+    }
+}
+
+fn from_private_pkey(pkey: &PKeyRef<Private>) -> Result<Keypair, Error> {
+    let algorithm = algorithm_of(pkey)?;
+    let error = move |e: ErrorStack| DecodingError::new(algorithm, "PEM private key").source(e);
+    match algorithm {
+        #[cfg(feature = "ed25519")]
+        Algorithm::ED25519 => {
+            let raw = Zeroizing::new(pkey.raw_private_key().map_err(error)?);
+            let mut seed = Zeroizing::new([0; ed25519::SecretKey::SIZE]);
+            seed.copy_from_slice(&raw);
+            let signing_key = ed25519_dalek::SigningKey::from_bytes(&seed);
+            let sk = ed25519::SecretKey { signing_key };
+            Ok(Keypair::Ed25519(Box::new(sk.into())))
+        }
+        #[cfg(feature = "rsa")]
+        Algorithm::RSA => {
+            let der = Zeroizing::new(pkey.private_key_to_pkcs8().map_err(error)?);
+            rsa::Keypair::from_pkcs8(&der).map(Keypair::Rsa)
+        }
+        #[cfg(feature = "secp256k1")]
+        Algorithm::SECP256K1 => {
+            let mut bytes = ec_secret(pkey).map_err(error)?;
+            let sk = secp256k1::SecretKey::from_bytes(&mut *bytes)?;
+            Ok(Keypair::Secp256k1(sk.into()))
+        }
+        #[cfg(feature = "p256")]
+        Algorithm::P256 => {
+            let sk = p256::SecretKey::from_bytes(&*ec_secret(pkey).map_err(error)?)?;
+            Ok(Keypair::P256(sk.into()))
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(Error::Unsupported { algorithm, operation: "PEM" }),
+    }
+}
+
+fn from_public_pkey(pkey: &PKeyRef<Public>) -> Result<PublicKey, Error> {
+    let algorithm = algorithm_of(pkey)?;
+    let error = move |e: ErrorStack| DecodingError::new(algorithm, "PEM public key").source(e);
+    match algorithm {
+        #[cfg(feature = "ed25519")]
+        Algorithm::ED25519 => {
+            let raw = pkey.raw_public_key().map_err(error)?;
+            let vk = ed25519_dalek::VerifyingKey::try_from(raw.as_slice()).map_err(|e| {
+                DecodingError::new(algorithm, "Ed25519 public key").source(e)
+            })?;
+            Ok(PublicKey::Ed25519(ed25519::PublicKey(vk)))
+        }
+        #[cfg(feature = "rsa")]
+        Algorithm::RSA => {
+            let der = pkey.rsa().and_then(|rsa| rsa.public_key_to_der_pkcs1()).map_err(error)?;
+            Ok(PublicKey::Rsa(rsa::PublicKey(der)))
+        }
+        #[cfg(feature = "secp256k1")]
+        Algorithm::SECP256K1 => {
+            let point = ec_public(pkey).map_err(error)?;
+            secp256k1::PublicKey::decode(&point).map(PublicKey::Secp256k1)
+        }
+        #[cfg(feature = "p256")]
+        Algorithm::P256 => {
+            let point = ec_public(pkey).map_err(error)?;
+            p256::PublicKey::decode(&point).map(PublicKey::P256)
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(Error::Unsupported { algorithm, operation: "PEM" }),
+    }
+}
+
+fn private_pkey(kp: &Keypair) -> Result<PKey<Private>, Error> {
+    match kp {
+        #[cfg(feature = "ed25519")]
+        Keypair::Ed25519(kp) => {
+            let seed = Zeroizing::new(kp.secret().signing_key.to_bytes());
+            converted(Algorithm::ED25519, PKey::private_key_from_raw_bytes(&*seed, Id::ED25519))
+        }
+        #[cfg(feature = "rsa")]
+        Keypair::Rsa(kp) => converted(Algorithm::RSA, PKey::private_key_from_pkcs8(&kp.bytes)),
+        #[cfg(feature = "secp256k1")]
+        Keypair::Secp256k1(kp) => {
+            let secret = Zeroizing::new(kp.secret().to_bytes());
+            let pkey = ec_private(Nid::SECP256K1, &*secret, &kp.public().encode());
+            converted(Algorithm::SECP256K1, pkey)
+        }
+        #[cfg(feature = "p256")]
+        Keypair::P256(kp) => {
+            let secret = Zeroizing::new(kp.secret().to_bytes());
+            let pkey = ec_private(Nid::X9_62_PRIME256V1, &*secret, &kp.public().encode());
+            converted(Algorithm::P256, pkey)
+        }
+        #[cfg(feature = "bls")]
+        Keypair::Bls(_) => {
+            Err(Error::Unsupported { algorithm: Algorithm::BLS, operation: "PEM" })
+        }
+    }
+}
+
+fn public_pkey(pk: &PublicKey) -> Result<PKey<Public>, Error> {
+    match pk {
+        #[cfg(feature = "ed25519")]
+        PublicKey::Ed25519(pk) => converted(
+            Algorithm::ED25519,
+            PKey::public_key_from_raw_bytes(pk.0.as_bytes(), Id::ED25519),
+        ),
+        #[cfg(feature = "rsa")]
+        PublicKey::Rsa(pk) => converted(
+            Algorithm::RSA,
+            openssl::rsa::Rsa::public_key_from_der_pkcs1(&pk.0).and_then(PKey::from_rsa),
+        ),
+        #[cfg(feature = "secp256k1")]
+        PublicKey::Secp256k1(pk) => {
+            converted(Algorithm::SECP256K1, ec_public_pkey(Nid::SECP256K1, &pk.encode()))
+        }
+        #[cfg(feature = "p256")]
+        PublicKey::P256(pk) => {
+            converted(Algorithm::P256, ec_public_pkey(Nid::X9_62_PRIME256V1, &pk.encode()))
+        }
+        #[cfg(feature = "bls")]
+        PublicKey::Bls(_) => {
+            Err(Error::Unsupported { algorithm: Algorithm::BLS, operation: "PEM" })
+        }
+    }
+}
+
+/// Map the failure of OpenSSL to convert or encode a key of `algorithm`, e.g.
+/// an RSA public key decoded from X.509 without validating its PKCS#1 key.
+fn converted<T>(algorithm: Algorithm, result: Result<T, ErrorStack>) -> Result<T, Error> {
+    result.map_err(|e| Error::Encoding { algorithm, source: Box::new(e) })
+}
+
+#[cfg(any(feature = "secp256k1", feature = "p256"))]
+fn ec_private(curve: Nid, secret: &[u8], public: &[u8]) -> Result<PKey<Private>, ErrorStack> {
+    let group = EcGroup::from_curve_name(curve)?;
+    let point = EcPoint::from_bytes(&group, public, &mut *BigNumContext::new()?)?;
+    let key = EcKey::from_private_components(&group, &*BigNum::from_slice(secret)?, &point)?;
+    PKey::from_ec_key(key)
+}
+
+#[cfg(any(feature = "secp256k1", feature = "p256"))]
+fn ec_public_pkey(curve: Nid, public: &[u8]) -> Result<PKey<Public>, ErrorStack> {
+    let group = EcGroup::from_curve_name(curve)?;
+    let point = EcPoint::from_bytes(&group, public, &mut *BigNumContext::new()?)?;
+    PKey::from_ec_key(EcKey::from_public_key(&group, &point)?)
+}
+
+/// The 32-byte big-endian secret scalar of an EC key.
+#[cfg(any(feature = "secp256k1", feature = "p256"))]
+fn ec_secret(pkey: &PKeyRef<Private>) -> Result<Zeroizing<Vec<u8>>, ErrorStack> {
+    Ok(Zeroizing::new(pkey.ec_key()?.private_key().to_vec_padded(32)?))
+}
+
+/// The compressed SEC1 encoding of the public point of an EC key.
+#[cfg(any(feature = "secp256k1", feature = "p256"))]
+fn ec_public<T: HasParams + openssl::pkey::HasPublic>(
+    pkey: &PKeyRef<T>,
+) -> Result<Vec<u8>, ErrorStack> {
+    let ec = pkey.ec_key()?;
+    let mut ctx = BigNumContext::new()?;
+    ec.public_key().to_bytes(ec.group(), PointConversionForm::COMPRESSED, &mut ctx)
+}
@@ -1,21 +1,32 @@
-use std::error;
+use std::fmt;
 use std::sync::Arc;
 
+use openssl::error::ErrorStack;
+use openssl::rsa::Rsa;
 use ring::signature::{KeyPair, RsaKeyPair};
 use serde::de::Error as SerdeError;
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 use super::{PublicKey, SecretKey};
-use crate::DecodingError;
+use crate::{Algorithm, DecodingError, Error};
 
 /// An RSA keypair.
+///
+/// The PKCS#8 encoding kept alongside the key is wiped when the keypair is
+/// dropped. ring does not wipe its own copy of the private key, so the keypair
+/// does not implement `ZeroizeOnDrop`.
+///
+/// Keypairs are compared in constant time, but hashed and ordered by their
+/// public key.
 #[derive(Clone)]
 pub struct Keypair {
     pub(crate) key: Arc<RsaKeyPair>,
-    bytes: Vec<u8>,
+    pub(crate) bytes: Vec<u8>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 struct Inner {
     data: Vec<u8>,
 }
@@ -36,31 +47,79 @@ impl<'de> Deserialize<'de> for Keypair {
         D: serde::Deserializer<'de>,
     {
         let inner = Inner::deserialize(deserializer)?;
-        Ok(Self::from_pkcs8(inner.data.as_ref())
-            .map_err(|_| SerdeError::custom("RSA Not in DER format"))?)
+        Self::from_pkcs8(inner.data.as_ref())
+            .map_err(|_| SerdeError::custom("RSA Not in DER format"))
+    }
+}
+
+impl Drop for Keypair {
+    fn drop(&mut self) { self.bytes.zeroize(); }
+}
+
+impl ConstantTimeEq for Keypair {
+    fn ct_eq(&self, other: &Self) -> Choice { self.bytes.ct_eq(&other.bytes) }
+}
+
+impl PartialEq for Keypair {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
+}
+
+impl Eq for Keypair {}
+
+impl PartialOrd for Keypair {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Keypair {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.public_bytes().cmp(other.public_bytes())
+    }
+}
+
+impl std::hash::Hash for Keypair {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.public_bytes().hash(state); }
+}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair").field("public", &self.public()).finish()
     }
 }
 
 impl Keypair {
     pub const SEC_PARAM: u32 = 2048;
 
-    pub fn generate() -> Result<Self, Box<dyn error::Error>> {
-        let key = openssl::rsa::Rsa::generate(Self::SEC_PARAM)?;
-        let der = key.private_key_to_der()?;
-        Self::from_pkcs8(&der).map_err(|e| e.into())
+    /// Generate a new RSA keypair with a modulus of `SEC_PARAM` bits.
+    pub fn generate() -> Result<Self, Error> {
+        let rsa = Rsa::generate(Self::SEC_PARAM).map_err(key_generation_error)?;
+        Self::from_openssl(rsa)
     }
+
+    fn from_openssl(rsa: Rsa<openssl::pkey::Private>) -> Result<Self, Error> {
+        // ring only accepts PKCS#8, whereas `Rsa::private_key_to_der` emits PKCS#1.
+        let der = openssl::pkey::PKey::from_rsa(rsa)
+            .and_then(|key| key.private_key_to_pkcs8())
+            .map_err(key_generation_error)?;
+        Self::from_pkcs8(&Zeroizing::new(der))
+    }
+
     /// Decode an RSA keypair from a DER-encoded private key in PKCS#8 PrivateKeyInfo
     /// format (i.e. unencrypted) as defined in [RFC5208].
     ///
     /// [RFC5208]: https://tools.ietf.org/html/rfc5208#section-5
-    pub fn from_pkcs8(der: &[u8]) -> Result<Keypair, DecodingError> {
-        let kp = RsaKeyPair::from_pkcs8(&der)
-            .map_err(|e| DecodingError::new("RSA PKCS#8 PrivateKeyInfo").source(e))?;
+    pub fn from_pkcs8(der: &[u8]) -> Result<Keypair, Error> {
+        let kp = RsaKeyPair::from_pkcs8(der)
+            .map_err(|e| DecodingError::new(Algorithm::RSA, "PKCS#8 PrivateKeyInfo").source(e))?;
         Ok(Keypair { key: Arc::new(kp), bytes: der.to_vec() })
     }
 
     /// Get the public key from the keypair.
-    pub fn public(&self) -> PublicKey { PublicKey(self.key.public_key().as_ref().to_vec()) }
+    pub fn public(&self) -> PublicKey { PublicKey(self.public_bytes().to_vec()) }
 
-    pub fn secret(&self) -> SecretKey { SecretKey(self.key.clone()) }
+    fn public_bytes(&self) -> &[u8] { self.key.public_key().as_ref() }
+
+    /// Get the secret key of this keypair.
+    pub fn secret(&self) -> SecretKey { SecretKey(self.clone()) }
 }
+
+fn key_generation_error(e: ErrorStack) -> Error { Error::key_generation(Algorithm::RSA, e) }
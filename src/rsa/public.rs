@@ -3,18 +3,40 @@ use std::fmt;
 use asn1_der::typed::{DerDecodable, DerEncodable, DerTypeView, Sequence};
 use asn1_der::{Asn1DerError, Asn1DerErrorVariant, DerObject, Sink, VecBacking};
 use ring::signature::{self, RSA_PKCS1_2048_8192_SHA256};
+use serde::{Deserialize, Serialize};
 
-use crate::DecodingError;
+use crate::{Algorithm, DecodingError, Error, VerificationError};
 
 /// An RSA public key.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PublicKey(pub(crate) Vec<u8>);
 
 impl PublicKey {
     /// Verify an RSA signature on a message using the public key.
-    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool { self.try_verify(msg, sig).is_ok() }
+
+    /// Verify an RSA signature like `verify`, but report why it is invalid.
+    ///
+    /// ring does not say why a signature is rejected, so apart from the
+    /// length check every failure is reported as an invalid signature.
+    pub fn try_verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
         let key = signature::UnparsedPublicKey::new(&RSA_PKCS1_2048_8192_SHA256, &self.0);
-        key.verify(msg, sig).is_ok()
+        match key.verify(msg, sig) {
+            Ok(()) => Ok(()),
+            Err(_) => match self.modulus_len() {
+                Some(expected) if expected != sig.len() => Err(VerificationError::InvalidLength {
+                    algorithm: "RSA",
+                    expected,
+                    actual: sig.len(),
+                }),
+                _ => Err(VerificationError::Invalid("RSA")),
+            },
+        }
+    }
+
+    /// The length of the modulus in bytes, which is also the length of signatures.
+    fn modulus_len(&self) -> Option<usize> {
+        openssl::rsa::Rsa::public_key_from_der_pkcs1(&self.0).ok().map(|rsa| rsa.size() as usize)
     }
 
     /// Encode the RSA public key in DER as a PKCS#1 RSAPublicKey structure,
@@ -39,16 +61,14 @@ impl PublicKey {
             subjectPublicKey: Asn1SubjectPublicKey(self.clone()),
         };
         let mut buf = Vec::new();
-        let buf =
-            spki.encode(&mut buf).map(|_| buf).expect("RSA X.509 public key encoding failed.");
-        buf
+        spki.encode(&mut buf).map(|_| buf).expect("RSA X.509 public key encoding failed.")
     }
 
     /// Decode an RSA public key from a DER-encoded X.509 SubjectPublicKeyInfo
     /// structure. See also `encode_x509`.
-    pub fn decode_x509(pk: &[u8]) -> Result<PublicKey, DecodingError> {
+    pub fn decode_x509(pk: &[u8]) -> Result<PublicKey, Error> {
         Asn1SubjectPublicKeyInfo::decode(pk)
-            .map_err(|e| DecodingError::new("RSA X.509").source(e))
+            .map_err(|e| DecodingError::new(Algorithm::RSA, "X.509").source(e).into())
             .map(|spki| spki.subjectPublicKey.0)
     }
 }
@@ -189,7 +209,7 @@ impl DerDecodable<'_> for Asn1SubjectPublicKey {
             )));
         }
 
-        let pk_der: Vec<u8> = object.value().into_iter().skip(1).cloned().collect();
+        let pk_der: Vec<u8> = object.value().iter().skip(1).cloned().collect();
         // We don't parse pk_der further as an ASN.1 RsaPublicKey, since
         // we only need the DER encoding for `verify`.
         Ok(Self(PublicKey(pk_der)))
@@ -1,21 +1,40 @@
-use std::sync::Arc;
+use std::fmt;
 
 use ring::rand::SystemRandom;
-use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+use ring::signature::RSA_PKCS1_SHA256;
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 
-use crate::SigningError;
+use super::Keypair;
+use crate::{Algorithm, Error, SigningError};
 
-#[derive(Debug, Clone)]
-pub struct SecretKey(pub(crate) Arc<RsaKeyPair>);
+/// An RSA secret key.
+///
+/// ring does not expose the private half of an `RsaKeyPair` on its own, so
+/// the secret key keeps the whole keypair (and its PKCS#8 encoding) around.
+///
+/// Secret keys are compared in constant time, and are deliberately neither
+/// `Hash` nor `Ord`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretKey(pub(crate) Keypair);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SecretKey") }
+}
+
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice { self.0.ct_eq(&other.0) }
+}
 
 impl SecretKey {
     /// Sign a message with this keypair.
-    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, SigningError> {
-        let mut signature = vec![0; self.0.public_modulus_len()];
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        let key = &self.0.key;
+        let mut signature = vec![0; key.public_modulus_len()];
         let rng = SystemRandom::new();
-        match self.0.sign(&RSA_PKCS1_SHA256, &rng, &data, &mut signature) {
+        match key.sign(&RSA_PKCS1_SHA256, &rng, data, &mut signature) {
             Ok(()) => Ok(signature),
-            Err(e) => Err(SigningError::new("RSA").source(e)),
+            Err(e) => Err(SigningError::new(Algorithm::RSA, "PKCS#1 v1.5").source(e).into()),
         }
     }
 }
@@ -2,15 +2,13 @@ use std::error::Error;
 use std::fmt;
 
 use quickcheck::*;
-use rand::seq::SliceRandom;
 use ring::signature::KeyPair;
 
 use super::*;
-use crate::SigningError;
 
-const KEY1: &'static [u8] = include_bytes!("test/rsa-2048.pk8");
-const KEY2: &'static [u8] = include_bytes!("test/rsa-3072.pk8");
-const KEY3: &'static [u8] = include_bytes!("test/rsa-4096.pk8");
+const KEY1: &[u8] = include_bytes!("test/rsa-2048.pk8");
+const KEY2: &[u8] = include_bytes!("test/rsa-3072.pk8");
+const KEY3: &[u8] = include_bytes!("test/rsa-4096.pk8");
 
 #[derive(Clone)]
 struct SomeKeypair(Keypair);
@@ -21,17 +19,16 @@ impl fmt::Debug for SomeKeypair {
 
 impl Arbitrary for SomeKeypair {
     fn arbitrary(generator: &mut Gen) -> SomeKeypair {
-        let mut rng = rand::thread_rng();
-        let mut key = [KEY1, KEY2, KEY3].choose(&mut rng).unwrap().to_vec();
-        SomeKeypair(Keypair::from_pkcs8(&mut key).unwrap())
+        let key = generator.choose(&[KEY1, KEY2, KEY3]).unwrap();
+        SomeKeypair(Keypair::from_pkcs8(key).unwrap())
     }
 }
 
 #[test]
 fn rsa_from_pkcs8() {
-    assert!(Keypair::from_pkcs8(&mut KEY1.to_vec()).is_ok());
-    assert!(Keypair::from_pkcs8(&mut KEY2.to_vec()).is_ok());
-    assert!(Keypair::from_pkcs8(&mut KEY3.to_vec()).is_ok());
+    assert!(Keypair::from_pkcs8(KEY1).is_ok());
+    assert!(Keypair::from_pkcs8(KEY2).is_ok());
+    assert!(Keypair::from_pkcs8(KEY3).is_ok());
 }
 
 #[test]
@@ -45,7 +42,7 @@ fn rsa_x509_encode_decode() {
 
 #[test]
 fn rsa_sign_verify() {
-    fn prop(SomeKeypair(kp): SomeKeypair, msg: Vec<u8>) -> Result<bool, SigningError> {
+    fn prop(SomeKeypair(kp): SomeKeypair, msg: Vec<u8>) -> Result<bool, crate::Error> {
         kp.secret().sign(&msg).map(|s| kp.public().verify(&msg, &s))
     }
     QuickCheck::new().tests(10).quickcheck(prop as fn(_, _) -> _);
@@ -61,3 +58,22 @@ fn test_decode_encode() {
     }
     QuickCheck::new().tests(10).quickcheck(prop as fn() -> _);
 }
+
+#[test]
+fn rsa_generic_keypair() -> Result<(), Box<dyn Error>> {
+    for key in [KEY1, KEY2, KEY3] {
+        let kp = crate::Keypair::rsa_from_pkcs8(&mut key.to_vec())?;
+        let msg = "hello world".as_bytes();
+        let sig = kp.private().sign(msg)?;
+        assert!(kp.public().verify(msg, &sig));
+        assert!(!kp.public().verify("h3ll0 w0rld".as_bytes(), &sig));
+
+        let decoded: crate::Keypair = bincode::deserialize(&bincode::serialize(&kp)?)?;
+        assert_eq!(decoded, kp);
+    }
+
+    let kp = crate::Keypair::generate_rsa()?;
+    let sig = kp.private().sign(&[1, 2, 3])?;
+    assert!(kp.public().verify(&[1, 2, 3], &sig));
+    Ok(())
+}
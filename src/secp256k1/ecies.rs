@@ -0,0 +1,68 @@
+//! ECIES for Secp256k1 keys.
+//!
+//! An ephemeral key is agreed with the recipient's key by ECDH, and the
+//! x-coordinate of the shared point is expanded with HKDF-SHA256 into a
+//! ChaCha20-Poly1305 key bound to both public keys. Every key is used for a
+//! single message, so the nonce is fixed to zero. The ciphertext is the
+//! compressed ephemeral public key followed by the sealed message.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use ring::hkdf;
+use zeroize::Zeroizing;
+
+use super::{PublicKey, SecretKey};
+use crate::Error;
+
+/// The HKDF info of the key, followed by the ephemeral and recipient public keys.
+const ECIES_INFO: &[u8] = b"libcrypto secp256k1 ecies v1";
+
+impl PublicKey {
+    /// Encrypt `msg` to the holder of the secret key with ECIES, authenticating
+    /// `aad` along with it.
+    pub fn encrypt(&self, msg: &[u8], aad: &[u8]) -> Vec<u8> {
+        let ephemeral = SecretKey::generate();
+        let enc = PublicKey(libsecp256k1::PublicKey::from_secret_key(&ephemeral.0));
+        let ciphertext = cipher(&ephemeral, self, &enc, self)
+            .encrypt(&Nonce::default(), Payload { msg, aad })
+            .expect("ChaCha20-Poly1305 encryption failed.");
+        [&enc.encode()[..], &ciphertext].concat()
+    }
+}
+
+impl SecretKey {
+    /// Decrypt a ciphertext of [`PublicKey::encrypt`] with the same `aad`.
+    pub fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        if ciphertext.len() < 33 {
+            return Err(Error::Decryption("ciphertext too short"));
+        }
+        let (enc, ciphertext) = ciphertext.split_at(33);
+        let enc =
+            PublicKey::decode(enc).map_err(|_| Error::Decryption("invalid ephemeral key"))?;
+        let recipient = PublicKey(libsecp256k1::PublicKey::from_secret_key(&self.0));
+        cipher(self, &enc, &enc, &recipient)
+            .decrypt(&Nonce::default(), Payload { msg: ciphertext, aad })
+            .map_err(|_| Error::Decryption("authentication failed"))
+    }
+}
+
+/// The cipher of the exchange between `sk` and `peer`, where `enc` is the
+/// ephemeral public key and `recipient` the public key of the recipient.
+fn cipher(
+    sk: &SecretKey,
+    peer: &PublicKey,
+    enc: &PublicKey,
+    recipient: &PublicKey,
+) -> ChaCha20Poly1305 {
+    let mut shared = peer.0;
+    shared.tweak_mul_assign(&sk.0).expect("Secret keys are non-zero");
+    let point = Zeroizing::new(shared.serialize_compressed());
+    let info = [ECIES_INFO, &enc.encode(), &recipient.encode()];
+    let mut key = Zeroizing::new([0; 32]);
+    hkdf::Salt::new(hkdf::HKDF_SHA256, &[])
+        .extract(&point[1..])
+        .expand(&info, hkdf::HKDF_SHA256)
+        .and_then(|okm| okm.fill(&mut *key))
+        .expect("HKDF-SHA256 can expand 32 bytes");
+    ChaCha20Poly1305::new(&(*key).into())
+}
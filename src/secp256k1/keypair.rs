@@ -1,11 +1,16 @@
 use std::fmt;
 
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use zeroize::ZeroizeOnDrop;
 
 use super::{PublicKey, SecretKey};
+use crate::Error;
 
 /// A Secp256k1 keypair.
-#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+///
+/// Keypairs are hashed and ordered by their public key.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Keypair {
     secret: SecretKey,
     public: PublicKey,
@@ -29,6 +34,15 @@ impl Keypair {
     /// Generate a new sec256k1 `Keypair`.
     pub fn generate() -> Keypair { Keypair::from(SecretKey::generate()) }
 
+    /// Generate a new keypair from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Keypair {
+        Keypair::from(SecretKey::generate_with_rng(rng))
+    }
+
+    /// Deterministically derive a keypair from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> Keypair { Keypair::from(SecretKey::from_seed(seed)) }
+
     /// Get the public key of this keypair.
     pub fn public(&self) -> &PublicKey { &self.public }
 
@@ -38,14 +52,16 @@ impl Keypair {
     /// Serialize the keypair
     pub fn to_bytes(&self) -> Vec<u8> { self.secret.to_bytes().to_vec() }
 
-    /// Deserialize the keypair
-    pub fn from_bytes(data: &mut [u8]) -> anyhow::Result<Self> {
+    /// Deserialize the keypair, zeroing the input slice on success.
+    pub fn from_bytes(data: &mut [u8]) -> Result<Self, Error> {
         let sk = SecretKey::from_bytes(data)?;
         let kpair = Keypair::from(sk);
         Ok(kpair)
     }
 }
 
+impl ZeroizeOnDrop for Keypair {}
+
 impl fmt::Debug for Keypair {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Keypair").field("public", &self.public).finish()
@@ -64,3 +80,15 @@ impl From<SecretKey> for Keypair {
 impl From<Keypair> for SecretKey {
     fn from(kp: Keypair) -> SecretKey { kp.secret }
 }
+
+impl std::hash::Hash for Keypair {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.public.hash(state); }
+}
+
+impl PartialOrd for Keypair {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Keypair {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.public.cmp(&other.public) }
+}
@@ -19,6 +19,9 @@
 // DEALINGS IN THE SOFTWARE.
 
 //! Secp256k1 keys.
+//!
+//! Besides ECDSA and Schnorr signatures, messages can be encrypted to a
+//! public key with ECIES, see [`PublicKey::encrypt`].
 
 mod keypair;
 pub use keypair::*;
@@ -29,5 +32,9 @@ pub use secret::*;
 mod public;
 pub use public::*;
 
+mod ecies;
+
+pub mod schnorr;
+
 #[cfg(test)]
 mod tests;
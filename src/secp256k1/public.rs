@@ -4,6 +4,8 @@ use libsecp256k1::{Message, Signature};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest as ShaDigestTrait, Sha256};
 
+use crate::{Algorithm, DecodingError, Error, VerificationError};
+
 /// A Secp256k1 public key.
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct PublicKey(pub(crate) libsecp256k1::PublicKey);
@@ -18,7 +20,7 @@ impl fmt::Debug for PublicKey {
     }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
+#[allow(clippy::derived_hash_with_manual_eq)]
 impl Hash for PublicKey {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.0.serialize().hash(state);
@@ -27,7 +29,7 @@ impl Hash for PublicKey {
 
 impl PartialOrd for PublicKey {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.serialize().partial_cmp(&other.0.serialize())
+        Some(self.cmp(other))
     }
 }
 
@@ -39,15 +41,29 @@ impl Ord for PublicKey {
 
 impl PublicKey {
     /// Verify the Secp256k1 signature on a message using the public key.
-    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
-        self.verify_hash(Sha256::digest(msg).as_ref(), sig)
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool { self.try_verify(msg, sig).is_ok() }
+
+    /// Verify the Secp256k1 signature on a message like `verify`, but report why it is invalid.
+    pub fn try_verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
+        self.try_verify_hash(Sha256::digest(msg).as_ref(), sig)
     }
 
     /// Verify the Secp256k1 DER-encoded signature on a raw 256-bit message using the public key.
     pub fn verify_hash(&self, msg: &[u8], sig: &[u8]) -> bool {
-        Message::parse_slice(msg)
-            .and_then(|m| Signature::parse_der(sig).map(|s| libsecp256k1::verify(&m, &s, &self.0)))
-            .unwrap_or(false)
+        self.try_verify_hash(msg, sig).is_ok()
+    }
+
+    /// Verify the signature on a raw 256-bit message like `verify_hash`, but
+    /// report why it is invalid.
+    pub fn try_verify_hash(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
+        let msg = Message::parse_slice(msg)
+            .map_err(|_| VerificationError::Malformed("Secp256k1 message hash"))?;
+        let sig = Signature::parse_der(sig)
+            .map_err(|_| VerificationError::Malformed("Secp256k1 DER signature"))?;
+        if !libsecp256k1::verify(&msg, &sig, &self.0) {
+            return Err(VerificationError::Invalid("Secp256k1"));
+        }
+        Ok(())
     }
 
     /// Encode the public key in compressed form, i.e. with one coordinate
@@ -59,11 +75,12 @@ impl PublicKey {
 
     /// Decode a public key from a byte slice in the the format produced
     /// by `encode`.
-    pub fn decode(k: &[u8]) -> anyhow::Result<PublicKey> {
+    pub fn decode(k: &[u8]) -> Result<PublicKey, Error> {
         let pk = libsecp256k1::PublicKey::parse_slice(
             k,
             Some(libsecp256k1::PublicKeyFormat::Compressed),
-        )?;
+        )
+        .map_err(|e| DecodingError::new(Algorithm::SECP256K1, "public key").source(e))?;
         Ok(PublicKey(pk))
     }
 }
@@ -0,0 +1,41 @@
+//! BIP-340 Schnorr signatures over Secp256k1.
+//!
+//! Public keys are x-only (32 bytes) and signatures are 64 bytes, as defined in
+//! [BIP-340]. Messages of any length are signed as-is, without pre-hashing.
+//!
+//! [BIP-340]: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+
+mod keypair;
+pub use keypair::*;
+
+mod public;
+pub use public::*;
+
+mod secret;
+pub use secret::*;
+
+#[cfg(test)]
+mod tests;
+
+use k256::elliptic_curve::ops::Reduce;
+use k256::{FieldBytes, Scalar, U256};
+use sha2::{Digest, Sha256};
+
+/// Size of a BIP-340 signature.
+pub const SIGNATURE_SIZE: usize = 64;
+
+const CHALLENGE_TAG: &[u8] = b"BIP0340/challenge";
+
+/// The BIP-340 challenge `e = int(hash_challenge(r || P || m)) mod n`.
+pub(crate) fn challenge(r: &[u8], pk: &[u8], msg: &[u8]) -> Scalar {
+    let tag = Sha256::digest(CHALLENGE_TAG);
+    let e: [u8; 32] = Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(r)
+        .chain_update(pk)
+        .chain_update(msg)
+        .finalize()
+        .into();
+    <Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::from(e))
+}
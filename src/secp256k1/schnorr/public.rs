@@ -0,0 +1,134 @@
+use std::{fmt, hash::Hash};
+
+use k256::elliptic_curve::{Field, PrimeField};
+use k256::schnorr::{Signature, VerifyingKey};
+use k256::{FieldBytes, ProjectivePoint, Scalar};
+use serde::{Deserialize, Serialize};
+
+use super::{challenge, SIGNATURE_SIZE};
+use crate::{Algorithm, DecodingError, Error, VerificationError};
+
+/// A BIP-340 x-only public key.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct PublicKey(pub(crate) VerifyingKey);
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PublicKey(x-only): ")?;
+        for byte in &self.encode() {
+            write!(f, "{:x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derived_hash_with_manual_eq)]
+impl Hash for PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.encode().hash(state); }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.encode().cmp(&other.encode()) }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Serialized([u8; 32]);
+
+impl Serialize for PublicKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Serialized(self.encode()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = Serialized::deserialize(deserializer)?;
+        PublicKey::decode(&data.0).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse a 64-byte BIP-340 signature.
+fn parse_signature(sig: &[u8]) -> Result<Signature, VerificationError> {
+    if sig.len() != SIGNATURE_SIZE {
+        return Err(VerificationError::InvalidLength {
+            algorithm: "Schnorr",
+            expected: SIGNATURE_SIZE,
+            actual: sig.len(),
+        });
+    }
+    Signature::try_from(sig).map_err(|_| VerificationError::Malformed("Schnorr signature"))
+}
+
+impl PublicKey {
+    /// Verify the BIP-340 signature on a message using the public key.
+    pub fn verify(&self, msg: &[u8], sig: &[u8]) -> bool { self.try_verify(msg, sig).is_ok() }
+
+    /// Verify the BIP-340 signature on a message like `verify`, but report why it is invalid.
+    pub fn try_verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), VerificationError> {
+        let sig = parse_signature(sig)?;
+        self.0.verify_raw(msg, &sig).map_err(|_| VerificationError::Invalid("Schnorr"))
+    }
+
+    /// Batch-verify multiple (message, signature, public_key) tuples with the
+    /// randomized check from BIP-340, which needs a single comparison of curve
+    /// points instead of one per signature.
+    ///
+    /// Returns true if ALL signatures are valid.
+    pub fn verify_batch(
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&PublicKey],
+    ) -> bool {
+        if messages.len() != signatures.len() || messages.len() != public_keys.len() {
+            return false;
+        }
+        let mut rng = rand::thread_rng();
+        let mut s_sum = Scalar::ZERO;
+        let mut rhs = ProjectivePoint::IDENTITY;
+        for (i, ((msg, sig), pk)) in messages.iter().zip(signatures).zip(public_keys).enumerate() {
+            if parse_signature(sig).is_err() {
+                return false;
+            }
+            let (r_bytes, s_bytes) = sig.split_at(32);
+            // lift_x(r): the point with x coordinate r and an even y coordinate.
+            let r = match VerifyingKey::from_bytes(r_bytes) {
+                Ok(r) => ProjectivePoint::from(*r.as_affine()),
+                Err(_) => return false,
+            };
+            let s = Scalar::from_repr(*FieldBytes::from_slice(s_bytes));
+            let s = match Option::<Scalar>::from(s) {
+                Some(s) => s,
+                None => return false,
+            };
+            let e = challenge(r_bytes, &pk.encode(), msg);
+            let a = if i == 0 { Scalar::ONE } else { Scalar::random(&mut rng) };
+            s_sum += a * s;
+            rhs += r * a + ProjectivePoint::from(*pk.0.as_affine()) * (a * e);
+        }
+        ProjectivePoint::GENERATOR * s_sum == rhs
+    }
+
+    /// Encode the public key as its 32-byte x coordinate.
+    pub fn encode(&self) -> [u8; 32] { self.0.to_bytes().into() }
+
+    /// Decode a public key from a byte slice in the format produced by `encode`.
+    pub fn decode(k: &[u8]) -> Result<PublicKey, Error> {
+        if k.len() != 32 {
+            let msg = "Schnorr public key must be 32 bytes";
+            return Err(DecodingError::new(Algorithm::SECP256K1, msg).into());
+        }
+        let pk = VerifyingKey::from_bytes(k)
+            .map_err(|e| DecodingError::new(Algorithm::SECP256K1, "Schnorr public key").source(e))?;
+        Ok(PublicKey(pk))
+    }
+}
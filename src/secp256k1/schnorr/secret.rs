@@ -0,0 +1,118 @@
+use std::fmt;
+
+use k256::schnorr::SigningKey;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::PublicKey;
+use crate::{Algorithm, DecodingError, Error, SigningError};
+
+/// A BIP-340 Schnorr secret key.
+///
+/// The key is wiped from memory when dropped.
+///
+/// Secret keys are compared in constant time, and are deliberately neither
+/// `Hash` nor `Ord`.
+#[derive(Clone)]
+pub struct SecretKey(pub(crate) SigningKey);
+
+/// The inner signing key wipes itself on drop.
+impl ZeroizeOnDrop for SecretKey {}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SecretKey") }
+}
+
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_nonzero_scalar().ct_eq(other.0.as_nonzero_scalar())
+    }
+}
+
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
+}
+
+impl Eq for SecretKey {}
+
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+struct Serialized([u8; 32]);
+
+impl Serialize for SecretKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Serialized(self.to_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = Serialized::deserialize(deserializer)?;
+        SecretKey::from_bytes(data.0).map_err(serde::de::Error::custom)
+    }
+}
+
+impl SecretKey {
+    /// Generate a new Schnorr secret key.
+    pub fn generate() -> SecretKey { Self::generate_with_rng(&mut rand::thread_rng()) }
+
+    /// Generate a new Schnorr secret key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SecretKey {
+        SecretKey(SigningKey::random(rng))
+    }
+
+    /// Deterministically derive a secret key from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> SecretKey {
+        Self::generate_with_rng(&mut crate::seeded_rng(seed))
+    }
+
+    /// Create a secret key from its 32 big-endian bytes.
+    pub fn from_bytes(sk: impl AsRef<[u8]>) -> Result<SecretKey, Error> {
+        let key = SigningKey::from_bytes(sk.as_ref())
+            .map_err(|e| DecodingError::new(Algorithm::SECP256K1, "Schnorr secret key").source(e))?;
+        Ok(SecretKey(key))
+    }
+
+    /// Returns the raw bytes of the secret key.
+    ///
+    /// BIP-340 negates keys whose public point has an odd y coordinate, so these
+    /// may differ from the bytes the key was created from.
+    pub fn to_bytes(&self) -> [u8; 32] { self.0.to_bytes().into() }
+
+    /// Get the x-only public key corresponding to this secret key.
+    pub fn public(&self) -> PublicKey { PublicKey(*self.0.verifying_key()) }
+
+    /// Sign a message with this secret key, producing a 64-byte BIP-340 signature.
+    /// Fresh auxiliary randomness is mixed into the nonce.
+    pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut aux_rand = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut aux_rand);
+        self.sign_with_aux_rand(msg, &aux_rand)
+    }
+
+    /// Sign a message with explicit auxiliary randomness, as in the BIP-340
+    /// reference implementation.
+    pub fn sign_with_aux_rand(&self, msg: &[u8], aux_rand: &[u8; 32]) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .0
+            .sign_raw(msg, aux_rand)
+            .map_err(|e| SigningError::new(Algorithm::SECP256K1, "Schnorr").source(e))?;
+        Ok(sig.to_bytes().to_vec())
+    }
+}
+
+/// Use an ECDSA Secp256k1 secret key for Schnorr signatures.
+impl From<&crate::secp256k1::SecretKey> for SecretKey {
+    fn from(sk: &crate::secp256k1::SecretKey) -> SecretKey {
+        // A valid libsecp256k1 key is always a valid non-zero scalar.
+        SecretKey::from_bytes(sk.to_bytes()).expect("valid secp256k1 secret key")
+    }
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+// Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+const VECTORS: [[&str; 5]; 3] = [
+    [
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+         25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+    ],
+    [
+        "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+         8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+    ],
+    [
+        "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+        "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+        "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+        "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+        "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1B\
+         AB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+    ],
+];
+
+#[test]
+fn schnorr_bip340_vectors() {
+    for [sk, pk, aux, msg, sig] in VECTORS {
+        let sk = SecretKey::from_bytes(hex(sk)).unwrap();
+        assert_eq!(sk.public().encode().to_vec(), hex(pk));
+        let aux: [u8; 32] = hex(aux).try_into().unwrap();
+        let signature = sk.sign_with_aux_rand(&hex(msg), &aux).unwrap();
+        assert_eq!(signature, hex(sig));
+        assert!(PublicKey::decode(&hex(pk)).unwrap().verify(&hex(msg), &signature));
+    }
+}
+
+#[test]
+fn schnorr_sign_verify() {
+    let kp = Keypair::generate();
+    let msg = "hello world".as_bytes();
+    let sig = kp.secret().sign(msg).unwrap();
+    assert_eq!(sig.len(), SIGNATURE_SIZE);
+    assert!(kp.public().verify(msg, &sig));
+    assert!(!kp.public().verify("h3ll0 w0rld".as_bytes(), &sig));
+    assert!(!kp.public().verify(msg, &sig[..32]));
+
+    let mut invalid_sig = sig.clone();
+    invalid_sig[40] ^= 1;
+    assert!(!kp.public().verify(msg, &invalid_sig));
+}
+
+#[test]
+fn schnorr_from_ecdsa_key() {
+    let ecdsa = crate::secp256k1::Keypair::generate();
+    let kp = Keypair::from(&ecdsa);
+    // The x-only key is the x coordinate of the compressed ECDSA key.
+    assert_eq!(kp.public().encode()[..], ecdsa.public().encode()[1..]);
+    let sig = kp.secret().sign(b"msg").unwrap();
+    assert!(kp.public().verify(b"msg", &sig));
+}
+
+#[test]
+fn schnorr_codec() {
+    let kp = Keypair::generate();
+    let decoded: Keypair = bincode::deserialize(&bincode::serialize(&kp).unwrap()).unwrap();
+    assert_eq!(decoded, kp);
+    assert_eq!(PublicKey::decode(&kp.public().encode()).unwrap(), *kp.public());
+    assert!(PublicKey::decode(&[0xff; 32]).is_err());
+}
+
+#[test]
+fn schnorr_verify_batch() {
+    let kps: Vec<Keypair> = (0..8).map(|_| Keypair::generate()).collect();
+    let msgs: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i; i as usize + 1]).collect();
+    let mut sigs: Vec<Vec<u8>> =
+        kps.iter().zip(&msgs).map(|(kp, m)| kp.secret().sign(m).unwrap()).collect();
+    let pks: Vec<&PublicKey> = kps.iter().map(|kp| kp.public()).collect();
+    let msg_refs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+
+    let sig_refs: Vec<&[u8]> = sigs.iter().map(|s| s.as_slice()).collect();
+    assert!(PublicKey::verify_batch(&msg_refs, &sig_refs, &pks));
+    assert!(PublicKey::verify_batch(&[], &[], &[]));
+    assert!(!PublicKey::verify_batch(&msg_refs[1..], &sig_refs, &pks));
+
+    sigs.swap(2, 3);
+    let sig_refs: Vec<&[u8]> = sigs.iter().map(|s| s.as_slice()).collect();
+    assert!(!PublicKey::verify_batch(&msg_refs, &sig_refs, &pks));
+}
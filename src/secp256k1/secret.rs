@@ -1,39 +1,53 @@
-use std::{fmt, hash::Hash};
+use std::fmt;
 
 use asn1_der::typed::{DerDecodable, Sequence};
 use libsecp256k1::Message;
-use rand::RngCore;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest as ShaDigestTrait, Sha256};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::derivation::ExtendedKey;
+use crate::{Algorithm, DecodingError, DerivationPath, Error, SigningError};
 
 /// A Secp256k1 secret key.
-#[derive(Clone, PartialEq, Eq)]
+///
+/// The key is wiped from memory when dropped.
+///
+/// Secret keys are compared in constant time, and are deliberately neither
+/// `Hash` nor `Ord`.
+#[derive(Clone)]
 pub struct SecretKey(pub(crate) libsecp256k1::SecretKey);
 
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) { self.0.clear(); }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) { self.zeroize(); }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
 impl fmt::Debug for SecretKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SecretKey") }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
-impl Hash for SecretKey {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.serialize().hash(state);
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let (a, b) = (Zeroizing::new(self.0.serialize()), Zeroizing::new(other.0.serialize()));
+        a.ct_eq(&*b)
     }
 }
 
-impl PartialOrd for SecretKey {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.serialize().partial_cmp(&other.0.serialize())
-    }
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
 }
 
-impl Ord for SecretKey {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.serialize().cmp(&other.0.serialize())
-    }
-}
+impl Eq for SecretKey {}
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 struct Serialized([u8; 32]);
 
 impl Serialize for SecretKey {
@@ -58,25 +72,60 @@ impl<'de> Deserialize<'de> for SecretKey {
 
 impl SecretKey {
     /// Generate a new Secp256k1 secret key.
-    pub fn generate() -> SecretKey {
-        let mut r = rand::thread_rng();
-        let mut b = [0; libsecp256k1::util::SECRET_KEY_SIZE];
+    pub fn generate() -> SecretKey { Self::generate_with_rng(&mut rand::thread_rng()) }
+
+    /// Generate a new Secp256k1 secret key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SecretKey {
+        let mut b = Zeroizing::new([0; libsecp256k1::util::SECRET_KEY_SIZE]);
         // This is how it is done in `secp256k1::SecretKey::random` which
         // we do not use here because it uses `rand::Rng` from rand-0.4.
         loop {
-            r.fill_bytes(&mut b);
+            rng.fill_bytes(&mut *b);
             if let Ok(k) = libsecp256k1::SecretKey::parse(&b) {
                 return SecretKey(k);
             }
         }
     }
 
+    /// Deterministically derive a secret key from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> SecretKey {
+        Self::generate_with_rng(&mut crate::seeded_rng(seed))
+    }
+
+    /// Derive the secret key at `path` from a master `seed` as specified by
+    /// [BIP-32]. Seeds should be between 16 and 64 bytes long.
+    ///
+    /// In the rare case (probability below 2^-127) that the seed or a child
+    /// index yields an invalid key, an error is returned, and BIP-32 suggests
+    /// to move on to the next index.
+    ///
+    /// [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+    pub fn derive(seed: &[u8], path: &DerivationPath) -> Result<SecretKey, Error> {
+        let invalid = |e| Error::key_generation(Algorithm::SECP256K1, e);
+        let mut node = ExtendedKey::master(b"Bitcoin seed", seed);
+        let mut sk = SecretKey(libsecp256k1::SecretKey::parse(&node.key).map_err(invalid)?);
+        for &index in path.indices() {
+            node = if DerivationPath::is_hardened(index) {
+                node.child(&[&[0][..], &*Zeroizing::new(sk.to_bytes())], index)
+            } else {
+                let pk = libsecp256k1::PublicKey::from_secret_key(&sk.0);
+                node.child(&[&pk.serialize_compressed()], index)
+            };
+            let tweak = SecretKey(libsecp256k1::SecretKey::parse(&node.key).map_err(invalid)?);
+            sk.0.tweak_add_assign(&tweak.0).map_err(invalid)?;
+        }
+        Ok(sk)
+    }
+
     /// Create a secret key from a byte slice, zeroing the slice on success.
     /// If the bytes do not constitute a valid Secp256k1 secret key, an
     /// error is returned.
-    pub fn from_bytes(mut sk: impl AsMut<[u8]>) -> anyhow::Result<SecretKey> {
+    pub fn from_bytes(mut sk: impl AsMut<[u8]>) -> Result<SecretKey, Error> {
         let sk_bytes = sk.as_mut();
-        let secret = libsecp256k1::SecretKey::parse_slice(&*sk_bytes)?;
+        let secret = libsecp256k1::SecretKey::parse_slice(&*sk_bytes)
+            .map_err(|e| DecodingError::new(Algorithm::SECP256K1, "secret key").source(e))?;
+        sk_bytes.zeroize();
         Ok(SecretKey(secret))
     }
 
@@ -84,13 +133,15 @@ impl SecretKey {
     /// structure as defined in [RFC5915], zeroing the input slice on success.
     ///
     /// [RFC5915]: https://tools.ietf.org/html/rfc5915
-    pub fn from_der(mut der: impl AsMut<[u8]>) -> anyhow::Result<SecretKey> {
+    pub fn from_der(mut der: impl AsMut<[u8]>) -> Result<SecretKey, Error> {
         // TODO: Stricter parsing.
         let der_obj = der.as_mut();
-        let obj: Sequence = DerDecodable::decode(der_obj)?;
-        let sk_obj = obj.get(1)?;
-        let mut sk_bytes: Vec<u8> = asn1_der::typed::DerDecodable::load(sk_obj)?;
+        let decoding_error = |e| DecodingError::new(Algorithm::SECP256K1, "ECPrivateKey").source(e);
+        let obj: Sequence = DerDecodable::decode(der_obj).map_err(decoding_error)?;
+        let sk_obj = obj.get(1).map_err(decoding_error)?;
+        let mut sk_bytes: Vec<u8> = DerDecodable::load(sk_obj).map_err(decoding_error)?;
         let sk = SecretKey::from_bytes(&mut sk_bytes)?;
+        der_obj.zeroize();
         Ok(sk)
     }
 
@@ -98,7 +149,7 @@ impl SecretKey {
     /// ECDSA signature, as defined in [RFC3278].
     ///
     /// [RFC3278]: https://tools.ietf.org/html/rfc3278#section-8.2
-    pub fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+    pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
         self.sign_hash(Sha256::digest(msg).as_ref())
     }
 
@@ -107,8 +158,9 @@ impl SecretKey {
 
     /// Sign a raw message of length 256 bits with this secret key, produces a DER-encoded
     /// ECDSA signature.
-    pub fn sign_hash(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
-        let m = Message::parse_slice(msg)?;
+    pub fn sign_hash(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        let m = Message::parse_slice(msg)
+            .map_err(|e| SigningError::new(Algorithm::SECP256K1, "message hash").source(e))?;
         Ok(libsecp256k1::sign(&m, &self.0).0.serialize_der().as_ref().into())
     }
 }
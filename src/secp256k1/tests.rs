@@ -1,4 +1,9 @@
+use openssl::ec::{EcGroup, EcKey};
+use openssl::nid::Nid;
+use zeroize::Zeroize;
+
 use super::*;
+use crate::tests::{assert_zeroize_on_drop, hex};
 
 #[test]
 fn secp256k1_secret_from_bytes() {
@@ -18,3 +23,70 @@ fn test_codec() {
     let new_kpair = new_kpair.unwrap();
     assert!(kpair.public() == new_kpair.public());
 }
+
+#[test]
+fn secp256k1_decoding_wipes_input() {
+    let sk = SecretKey::generate();
+    let mut bytes = sk.to_bytes();
+    SecretKey::from_bytes(&mut bytes).unwrap();
+    assert_eq!(bytes, [0; 32]);
+
+    let group = EcGroup::from_curve_name(Nid::SECP256K1).unwrap();
+    let mut der = EcKey::generate(&group).unwrap().private_key_to_der().unwrap();
+    SecretKey::from_der(&mut der).unwrap();
+    assert!(der.iter().all(|b| *b == 0));
+
+    let mut garbage = [0xff; 32];
+    assert!(SecretKey::from_bytes(&mut garbage).is_err());
+    assert_eq!(garbage, [0xff; 32]);
+}
+
+#[test]
+fn secp256k1_secret_is_wiped() {
+    let mut sk = SecretKey::generate();
+    sk.zeroize();
+    assert_eq!(sk.to_bytes(), [0; 32]);
+
+    assert_zeroize_on_drop::<SecretKey>();
+    assert_zeroize_on_drop::<Keypair>();
+}
+
+#[test]
+fn secp256k1_bip32_vectors() {
+    // Test vector 1 of BIP-32.
+    let seed = hex("000102030405060708090a0b0c0d0e0f");
+    let vectors = [
+        ("m", "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"),
+        ("m/0'", "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea"),
+        ("m/0'/1", "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368"),
+        ("m/0'/1/2'", "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca"),
+        ("m/0'/1/2'/2", "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4"),
+        (
+            "m/0'/1/2'/2/1000000000",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+        ),
+    ];
+    for (path, secret) in vectors {
+        let sk = SecretKey::derive(&seed, &path.parse().unwrap()).unwrap();
+        assert_eq!(sk.to_bytes().to_vec(), hex(secret), "{}", path);
+    }
+}
+
+#[test]
+fn secp256k1_ecies() {
+    let kp = Keypair::generate();
+    let ct = kp.public().encrypt(b"share", b"epoch 1");
+    assert_eq!(ct.len(), 33 + 5 + 16);
+    assert_eq!(kp.secret().decrypt(&ct, b"epoch 1").unwrap(), b"share");
+    assert_ne!(kp.public().encrypt(b"share", b"epoch 1"), ct);
+
+    assert!(kp.secret().decrypt(&ct, b"epoch 2").is_err());
+    assert!(Keypair::generate().secret().decrypt(&ct, b"epoch 1").is_err());
+    assert!(kp.secret().decrypt(&ct[..32], b"epoch 1").is_err());
+    let mut tampered = ct.clone();
+    tampered[0] = 0x05;
+    assert!(kp.secret().decrypt(&tampered, b"epoch 1").is_err());
+    let mut tampered = ct;
+    tampered[40] ^= 1;
+    assert!(kp.secret().decrypt(&tampered, b"epoch 1").is_err());
+}
@@ -0,0 +1,192 @@
+//! Shamir secret sharing of arbitrary byte strings.
+//!
+//! A secret is split into `n` shares such that any `t` of them reconstruct it,
+//! while fewer reveal nothing about it. The secret is cut into 31-byte chunks,
+//! each shared with its own random polynomial of degree `t - 1` over the
+//! scalar field of Ed25519, so that every share holds one field element per
+//! chunk.
+//!
+//! Nodes are identified by their index `0..n`; the share of node `i` is the
+//! evaluation of the polynomials at `i + 1`, like the key shares of the
+//! `threshold` module.
+//!
+//! Shares carry a random identifier of their split, so that mixing shares of
+//! different secrets is detected. Reconstructing from more than `t` shares
+//! also checks that the extra shares agree with the others, but corrupted
+//! shares cannot be detected among exactly `t` shares.
+
+use std::fmt;
+
+use curve25519_dalek::Scalar;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{Error, SharingError};
+
+/// The number of secret bytes in each field element.
+const CHUNK_LEN: usize = 31;
+
+/// The share of a secret held by one node.
+///
+/// The share is wiped from memory when dropped.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct Share {
+    index: usize,
+    threshold: usize,
+    split_id: [u8; 16],
+    len: usize,
+    values: Vec<Scalar>,
+}
+
+impl Share {
+    /// The index of the node holding this share.
+    pub fn index(&self) -> usize { self.index }
+
+    /// The number of shares needed to reconstruct the secret.
+    pub fn threshold(&self) -> usize { self.threshold }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .finish()
+    }
+}
+
+/// Split `secret` into `nodes` shares, any `threshold` of which reconstruct it.
+pub fn split(secret: &[u8], threshold: usize, nodes: usize) -> Result<Vec<Share>, Error> {
+    split_with_rng(secret, threshold, nodes, &mut rand::thread_rng())
+}
+
+/// Split `secret` like [`split`], drawing the polynomials from `rng`.
+pub fn split_with_rng<R: CryptoRng + RngCore>(
+    secret: &[u8],
+    threshold: usize,
+    nodes: usize,
+    rng: &mut R,
+) -> Result<Vec<Share>, Error> {
+    if threshold == 0 || threshold > nodes {
+        return Err(SharingError::InvalidThreshold { threshold, nodes }.into());
+    }
+    let mut split_id = [0; 16];
+    rng.fill_bytes(&mut split_id);
+    let polys: Vec<Zeroizing<Vec<Scalar>>> = secret
+        .chunks(CHUNK_LEN)
+        .map(|chunk| {
+            let mut coeffs = Zeroizing::new(Vec::with_capacity(threshold));
+            coeffs.push(encode_chunk(chunk));
+            coeffs.extend((1..threshold).map(|_| Scalar::random(&mut *rng)));
+            coeffs
+        })
+        .collect();
+    let shares = (0..nodes)
+        .map(|index| Share {
+            index,
+            threshold,
+            split_id,
+            len: secret.len(),
+            values: polys.iter().map(|coeffs| evaluate(coeffs, point(index))).collect(),
+        })
+        .collect();
+    Ok(shares)
+}
+
+/// Reconstruct the secret from at least `threshold` shares of the same split.
+///
+/// Fails if there are too few shares, if two shares have the same index, or if
+/// the shares are found to disagree.
+pub fn reconstruct(shares: &[Share]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let first = shares.first().ok_or(SharingError::NotEnoughShares { threshold: 1, got: 0 })?;
+    for (i, share) in shares.iter().enumerate() {
+        let same_split = share.split_id == first.split_id
+            && share.threshold == first.threshold
+            && share.len == first.len
+            && share.values.len() == first.values.len();
+        if !same_split {
+            return Err(SharingError::InconsistentShares.into());
+        }
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(SharingError::DuplicateShare { index: share.index }.into());
+        }
+    }
+    if first.len.div_ceil(CHUNK_LEN) != first.values.len() {
+        return Err(SharingError::InconsistentShares.into());
+    }
+    let threshold = first.threshold;
+    if threshold == 0 {
+        return Err(SharingError::InconsistentShares.into());
+    }
+    if shares.len() < threshold {
+        let got = shares.len();
+        return Err(SharingError::NotEnoughShares { threshold, got }.into());
+    }
+
+    let (used, extra) = shares.split_at(threshold);
+    let indices: Vec<usize> = used.iter().map(|share| share.index).collect();
+    for share in extra {
+        let lagrange = lagrange(&indices, point(share.index));
+        if (0..first.values.len()).any(|c| interpolate(used, &lagrange, c) != share.values[c]) {
+            return Err(SharingError::InconsistentShares.into());
+        }
+    }
+
+    let lagrange = lagrange(&indices, Scalar::ZERO);
+    let mut secret = Zeroizing::new(Vec::with_capacity(first.values.len() * CHUNK_LEN));
+    for c in 0..first.values.len() {
+        let bytes = Zeroizing::new(interpolate(used, &lagrange, c).to_bytes());
+        if bytes[CHUNK_LEN] != 0 {
+            return Err(SharingError::InconsistentShares.into());
+        }
+        secret.extend_from_slice(&bytes[..CHUNK_LEN]);
+    }
+    // The padding of the last chunk must be zero as well.
+    if secret[first.len..].iter().any(|&b| b != 0) {
+        return Err(SharingError::InconsistentShares.into());
+    }
+    secret.truncate(first.len);
+    Ok(secret)
+}
+
+/// A chunk of at most 31 bytes as a field element, in little-endian.
+fn encode_chunk(chunk: &[u8]) -> Scalar {
+    let mut bytes = Zeroizing::new([0; 32]);
+    bytes[..chunk.len()].copy_from_slice(chunk);
+    Scalar::from_bytes_mod_order(*bytes)
+}
+
+/// The evaluation point of node `index`.
+fn point(index: usize) -> Scalar { Scalar::from(index as u64) + Scalar::ONE }
+
+/// Evaluate a polynomial at `x` using Horner's rule.
+fn evaluate(coeffs: &[Scalar], x: Scalar) -> Scalar {
+    coeffs.iter().rev().fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+/// Interpolate chunk `c` of `shares` with the given Lagrange coefficients.
+fn interpolate(shares: &[Share], lagrange: &[Scalar], c: usize) -> Scalar {
+    shares.iter().zip(lagrange).map(|(share, l)| share.values[c] * l).sum()
+}
+
+/// The Lagrange coefficients for interpolating at `x` from the given nodes.
+///
+/// The indices must be distinct.
+fn lagrange(indices: &[usize], x: Scalar) -> Vec<Scalar> {
+    indices
+        .iter()
+        .map(|&i| {
+            let xi = point(i);
+            let (num, den) = indices.iter().filter(|&&j| j != i).fold(
+                (Scalar::ONE, Scalar::ONE),
+                |(num, den), &j| {
+                    let xj = point(j);
+                    (num * (x - xj), den * (xi - xj))
+                },
+            );
+            // The denominator is non-zero since the indices are distinct.
+            num * den.invert()
+        })
+        .collect()
+}
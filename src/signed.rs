@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::hash::Hash;
+use crate::{Domain, Error, Keypair, PublicKey, Signature, VerificationError};
+
+/// A type whose values can be signed in a [`Signed`] envelope.
+///
+/// Each type has its own domain, so that a signature on a value of one type
+/// never verifies for a value of another type with the same serialization.
+pub trait Signable: Serialize {
+    /// The domain the payloads of this type are hashed in.
+    const DOMAIN: Domain;
+}
+
+/// A payload of type `T` together with the identity of its signer and a
+/// signature on `Hash<T>` of the payload in `T::DOMAIN`.
+///
+/// Since the hash is bound to the domain of `T`, a `Signed<T>` can only be
+/// checked against a value of the same type it was created for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signed<T> {
+    pub(crate) data: T,
+    pub(crate) signer: PublicKey,
+    pub(crate) signature: Signature,
+}
+
+impl<T> Signed<T>
+where
+    T: Signable,
+{
+    /// Sign `data` with `keypair`.
+    pub fn new(data: T, keypair: &Keypair) -> Result<Self, Error> {
+        let hash = Hash::<T>::ser_and_hash_in(&T::DOMAIN, &data);
+        let signature = keypair.private().sign_typed(hash.as_ref())?;
+        Ok(Self { data, signer: keypair.public(), signature })
+    }
+
+    /// Check that the payload has been signed by `public_key`, and return it if so.
+    pub fn verify(&self, public_key: &PublicKey) -> Result<&T, Error> {
+        if public_key != &self.signer {
+            return Err(VerificationError::UnexpectedSigner.into());
+        }
+        let hash = Hash::<T>::ser_and_hash_in(&T::DOMAIN, &self.data);
+        public_key.try_verify_signature(hash.as_ref(), &self.signature)?;
+        Ok(&self.data)
+    }
+}
+
+impl<T> Signed<T> {
+    /// The payload, without checking the signature.
+    pub fn data(&self) -> &T { &self.data }
+
+    /// The public key of the claimed signer.
+    pub fn signer(&self) -> &PublicKey { &self.signer }
+
+    /// The signature on the hash of the payload.
+    pub fn signature(&self) -> &Signature { &self.signature }
+
+    /// Take the payload out of the envelope, without checking the signature.
+    pub fn into_inner(self) -> T { self.data }
+}
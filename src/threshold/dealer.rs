@@ -0,0 +1,27 @@
+use blstrs::G1Projective;
+use group::Group;
+
+use super::{poly, PublicKeySet, SecretKeyShare};
+use crate::{bls, Error, ThresholdError};
+
+/// Deal a fresh `threshold`-of-`n` key as a trusted dealer.
+///
+/// The dealer learns the group secret key, so this is meant for tests and
+/// deployments with a trusted setup. Use [`super::dkg`] otherwise.
+pub fn deal(threshold: usize, n: usize) -> Result<(PublicKeySet, Vec<SecretKeyShare>), Error> {
+    if threshold == 0 || threshold > n {
+        return Err(ThresholdError::InvalidThreshold { threshold, nodes: n }.into());
+    }
+    let mut rng = rand::thread_rng();
+    let mut coeffs = poly::random(threshold, &mut rng);
+    let commitment = coeffs.iter().map(|c| G1Projective::generator() * c).collect();
+    let shares = (0..n)
+        .map(|i| {
+            let secret = bls::SecretKey(poly::evaluate(&coeffs, poly::point(i)));
+            SecretKeyShare::new(i, secret)
+        })
+        .collect();
+    // The constant coefficient is the group secret key.
+    coeffs.iter_mut().for_each(bls::clear_scalar);
+    Ok((PublicKeySet::from_commitment(commitment, n), shares))
+}
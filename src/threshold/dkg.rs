@@ -0,0 +1,173 @@
+//! Distributed key generation following Pedersen's joint-Feldman protocol.
+//!
+//! Every node acts as a dealer of a random polynomial: it broadcasts a
+//! [`Commitment`] to the polynomial and privately sends each other node its
+//! [`Share`]. Each node checks the shares it receives against the broadcast
+//! commitments, and the final key is the sum of the contributions of all dealers
+//! that passed the check. Nobody learns the group secret key.
+//!
+//! Complaints against faulty dealers are left to the caller: all honest nodes must
+//! agree (e.g. through consensus) on the same set of dealers before calling
+//! [`Participant::finish`], otherwise they end up with incompatible keys.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use blstrs::{G1Projective, Scalar};
+use ff::Field;
+use group::Group;
+use serde::{Deserialize, Serialize};
+use zeroize::ZeroizeOnDrop;
+
+use super::{poly, PublicKeySet, SecretKeyShare};
+use crate::{bls, Error, ThresholdError};
+
+/// A dealer's broadcast commitment to its secret polynomial.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commitment(Vec<bls::PublicKey>);
+
+impl Commitment {
+    fn coefficients(&self) -> Vec<G1Projective> {
+        self.0.iter().map(|c| G1Projective::from(c.0)).collect()
+    }
+}
+
+/// The evaluation of a dealer's secret polynomial for a single recipient.
+///
+/// Must be sent over a private, authenticated channel.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Share {
+    dealer: usize,
+    recipient: usize,
+    value: bls::SecretKey,
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("dealer", &self.dealer)
+            .field("recipient", &self.recipient)
+            .finish()
+    }
+}
+
+// The value is a `bls::SecretKey`, which wipes itself on drop.
+impl ZeroizeOnDrop for Share {}
+
+/// The state of a single node during key generation.
+///
+/// The secret polynomial and the received shares are wiped when dropped.
+pub struct Participant {
+    index: usize,
+    threshold: usize,
+    n: usize,
+    coeffs: Vec<Scalar>,
+    received: BTreeMap<usize, (Vec<G1Projective>, Scalar)>,
+}
+
+impl fmt::Debug for Participant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Participant")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("n", &self.n)
+            .finish()
+    }
+}
+
+impl Drop for Participant {
+    fn drop(&mut self) {
+        self.coeffs.iter_mut().for_each(bls::clear_scalar);
+        self.received.values_mut().for_each(|(_, value)| bls::clear_scalar(value));
+    }
+}
+
+impl ZeroizeOnDrop for Participant {}
+
+impl Participant {
+    /// Start key generation as node `index` of `n`, for a `threshold`-of-`n` key.
+    pub fn new(index: usize, threshold: usize, n: usize) -> Result<Self, Error> {
+        if threshold == 0 || threshold > n {
+            return Err(ThresholdError::InvalidThreshold { threshold, nodes: n }.into());
+        }
+        if index >= n {
+            return Err(ThresholdError::InvalidIndex { index, nodes: n }.into());
+        }
+        let mut coeffs = poly::random(threshold, &mut rand::thread_rng());
+        // A zero secret would make our commitment the identity, which does not decode.
+        while bool::from(coeffs[0].is_zero()) {
+            coeffs = poly::random(threshold, &mut rand::thread_rng());
+        }
+        Ok(Self { index, threshold, n, coeffs, received: BTreeMap::new() })
+    }
+
+    /// The commitment to broadcast to all nodes.
+    pub fn commitment(&self) -> Commitment {
+        Commitment(
+            self.coeffs
+                .iter()
+                .map(|c| bls::PublicKey((G1Projective::generator() * c).into()))
+                .collect(),
+        )
+    }
+
+    /// The share to send privately to node `recipient`.
+    pub fn share_for(&self, recipient: usize) -> Share {
+        let value = poly::evaluate(&self.coeffs, poly::point(recipient));
+        Share { dealer: self.index, recipient, value: bls::SecretKey(value) }
+    }
+
+    /// Check a share received from a dealer against that dealer's commitment, and
+    /// record the dealer's contribution if it is valid.
+    pub fn receive(&mut self, commitment: &Commitment, share: &Share) -> Result<(), Error> {
+        if share.recipient != self.index {
+            return Err(ThresholdError::WrongRecipient { index: share.recipient }.into());
+        }
+        if share.dealer >= self.n {
+            return Err(ThresholdError::InvalidIndex { index: share.dealer, nodes: self.n }.into());
+        }
+        if commitment.0.len() != self.threshold {
+            return Err(ThresholdError::InvalidCommitment { dealer: share.dealer }.into());
+        }
+        let coeffs = commitment.coefficients();
+        let expected = poly::evaluate_commitment(&coeffs, poly::point(self.index));
+        if G1Projective::generator() * share.value.0 != expected {
+            return Err(ThresholdError::InvalidShare { index: share.dealer }.into());
+        }
+        self.received.insert(share.dealer, (coeffs, share.value.0));
+        Ok(())
+    }
+
+    /// The dealers whose contributions have been accepted so far.
+    pub fn dealers(&self) -> Vec<usize> { self.received.keys().copied().collect() }
+
+    /// Combine the contributions of the agreed set of `dealers` into this node's
+    /// key share and the public key set.
+    ///
+    /// At least `threshold` dealers are required so that the group secret is
+    /// unknown to any coalition below the threshold.
+    pub fn finish(self, dealers: &[usize]) -> Result<(PublicKeySet, SecretKeyShare), Error> {
+        let mut dealers = dealers.to_vec();
+        dealers.sort_unstable();
+        dealers.dedup();
+        if dealers.len() < self.threshold {
+            let (threshold, got) = (self.threshold, dealers.len());
+            return Err(ThresholdError::NotEnoughShares { threshold, got }.into());
+        }
+        let mut commitment = vec![G1Projective::identity(); self.threshold];
+        let mut secret = Scalar::ZERO;
+        for dealer in dealers {
+            let (coeffs, value) = self
+                .received
+                .get(&dealer)
+                .ok_or(ThresholdError::MissingShare { dealer })?;
+            for (acc, c) in commitment.iter_mut().zip(coeffs) {
+                *acc += c;
+            }
+            secret += value;
+        }
+        let share = SecretKeyShare::new(self.index, bls::SecretKey(secret));
+        bls::clear_scalar(&mut secret);
+        Ok((PublicKeySet::from_commitment(commitment, self.n), share))
+    }
+}
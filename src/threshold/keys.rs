@@ -0,0 +1,159 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use blstrs::{G1Projective, G2Projective};
+use group::Group;
+use serde::{Deserialize, Serialize};
+use zeroize::ZeroizeOnDrop;
+
+use super::poly;
+use crate::bls;
+use crate::bls::decode_signature;
+use crate::{Error, ThresholdError};
+
+/// The secret key share of a single node.
+///
+/// The share is wiped from memory when dropped.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretKeyShare {
+    index: usize,
+    secret: bls::SecretKey,
+}
+
+impl fmt::Debug for SecretKeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretKeyShare").field("index", &self.index).finish()
+    }
+}
+
+impl ZeroizeOnDrop for SecretKeyShare {}
+
+impl SecretKeyShare {
+    pub(crate) fn new(index: usize, secret: bls::SecretKey) -> Self { Self { index, secret } }
+
+    /// The index of the node holding this share.
+    pub fn index(&self) -> usize { self.index }
+
+    /// The verification key of this share.
+    pub fn public(&self) -> bls::PublicKey { self.secret.public() }
+
+    /// Sign a message with this share.
+    pub fn sign(&self, msg: &[u8]) -> Result<SignatureShare, Error> {
+        Ok(SignatureShare { index: self.index, sig: self.secret.sign(msg)? })
+    }
+}
+
+/// A signature share produced by a single node.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SignatureShare {
+    index: usize,
+    sig: Vec<u8>,
+}
+
+impl SignatureShare {
+    /// The index of the node that produced this share.
+    pub fn index(&self) -> usize { self.index }
+
+    /// The compressed BLS signature of this share.
+    pub fn as_bytes(&self) -> &[u8] { &self.sig }
+}
+
+/// The public information of a threshold key: the number of nodes and the
+/// Feldman commitment to the secret polynomial, from which the group public
+/// key and the verification key of every node are derived.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedPublicKeySet")]
+pub struct PublicKeySet {
+    nodes: usize,
+    commitment: Vec<bls::PublicKey>,
+}
+
+#[derive(Deserialize)]
+struct UncheckedPublicKeySet {
+    nodes: usize,
+    commitment: Vec<bls::PublicKey>,
+}
+
+impl TryFrom<UncheckedPublicKeySet> for PublicKeySet {
+    type Error = &'static str;
+
+    fn try_from(set: UncheckedPublicKeySet) -> Result<Self, Self::Error> {
+        let UncheckedPublicKeySet { nodes, commitment } = set;
+        if commitment.is_empty() {
+            return Err("empty threshold key commitment");
+        }
+        if commitment.len() > nodes {
+            return Err("threshold larger than the number of nodes");
+        }
+        Ok(Self { nodes, commitment })
+    }
+}
+
+impl From<PublicKeySet> for Vec<bls::PublicKey> {
+    fn from(set: PublicKeySet) -> Self { set.commitment }
+}
+
+impl PublicKeySet {
+    pub(crate) fn from_commitment(commitment: Vec<G1Projective>, nodes: usize) -> Self {
+        let commitment = commitment.into_iter().map(|c| bls::PublicKey(c.into())).collect();
+        Self { nodes, commitment }
+    }
+
+    fn coefficients(&self) -> Vec<G1Projective> {
+        self.commitment.iter().map(|c| G1Projective::from(c.0)).collect()
+    }
+
+    /// The number of shares needed to produce a signature.
+    pub fn threshold(&self) -> usize { self.commitment.len() }
+
+    /// The number of nodes holding a share.
+    pub fn nodes(&self) -> usize { self.nodes }
+
+    /// The group public key, under which combined signatures verify.
+    pub fn group_key(&self) -> &bls::PublicKey { &self.commitment[0] }
+
+    /// The group public key as a generic `PublicKey`.
+    pub fn public_key(&self) -> crate::PublicKey { crate::PublicKey::Bls(*self.group_key()) }
+
+    /// The verification key of the share held by node `index`.
+    pub fn verification_key(&self, index: usize) -> bls::PublicKey {
+        let vk = poly::evaluate_commitment(&self.coefficients(), poly::point(index));
+        bls::PublicKey(vk.into())
+    }
+
+    /// Verify a signature share on a message against the verification key of its node.
+    ///
+    /// Shares of nodes outside `0..nodes` are invalid.
+    pub fn verify_share(&self, msg: &[u8], share: &SignatureShare) -> bool {
+        share.index < self.nodes && self.verification_key(share.index).verify(msg, &share.sig)
+    }
+
+    /// Combine signature shares from at least `threshold` distinct nodes into a
+    /// signature that verifies under the group public key.
+    ///
+    /// Only the first `threshold` shares are used. Shares are not verified here, so
+    /// callers should check them with `verify_share` beforehand, or verify the
+    /// combined signature against `group_key`. Fails if a share is from a node
+    /// outside `0..nodes`.
+    pub fn combine(&self, shares: &[SignatureShare]) -> Result<Vec<u8>, Error> {
+        if let Some(share) = shares.iter().find(|s| s.index >= self.nodes) {
+            let (index, nodes) = (share.index, self.nodes);
+            return Err(ThresholdError::InvalidIndex { index, nodes }.into());
+        }
+        let mut seen = BTreeSet::new();
+        let shares: Vec<&SignatureShare> =
+            shares.iter().filter(|s| seen.insert(s.index)).take(self.threshold()).collect();
+        if shares.len() < self.threshold() {
+            let (threshold, got) = (self.threshold(), shares.len());
+            return Err(ThresholdError::NotEnoughShares { threshold, got }.into());
+        }
+        let indices: Vec<usize> = shares.iter().map(|s| s.index).collect();
+        let mut sig = G2Projective::identity();
+        for (share, lambda) in shares.iter().zip(poly::lagrange_at_zero(&indices)) {
+            let point = decode_signature(&share.sig)
+                .ok_or(ThresholdError::InvalidShare { index: share.index })?;
+            sig += point * lambda;
+        }
+        Ok(sig.to_compressed().to_vec())
+    }
+}
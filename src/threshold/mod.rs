@@ -0,0 +1,26 @@
+//! Threshold BLS12-381 signatures.
+//!
+//! A secret key is shared among `n` nodes such that any `t` of them can produce a
+//! signature. Each node signs with its [`SecretKeyShare`], the resulting
+//! [`SignatureShare`]s are checked against the node's verification key in the
+//! [`PublicKeySet`], and any `t` valid shares are combined by Lagrange interpolation
+//! into an ordinary BLS signature under the group public key.
+//!
+//! Keys are set up either by a trusted dealer ([`deal`]) or by a distributed key
+//! generation among the nodes themselves ([`dkg`]).
+//!
+//! Nodes are identified by their index `0..n`; the share of node `i` is the
+//! evaluation of the secret polynomial at `i + 1`.
+
+mod keys;
+pub use keys::*;
+
+mod dealer;
+pub use dealer::*;
+
+pub mod dkg;
+
+mod poly;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,45 @@
+//! Polynomial helpers over the BLS12-381 scalar field.
+
+use blstrs::{G1Projective, Scalar};
+use ff::Field;
+use group::Group;
+use rand::{CryptoRng, RngCore};
+
+/// The evaluation point of node `index`.
+pub(crate) fn point(index: usize) -> Scalar { Scalar::from(index as u64) + Scalar::ONE }
+
+/// A random polynomial of degree `threshold - 1`, lowest coefficient first.
+pub(crate) fn random<R: CryptoRng + RngCore>(threshold: usize, rng: &mut R) -> Vec<Scalar> {
+    (0..threshold).map(|_| Scalar::random(&mut *rng)).collect()
+}
+
+/// Evaluate a polynomial at `x` using Horner's rule.
+pub(crate) fn evaluate(coeffs: &[Scalar], x: Scalar) -> Scalar {
+    coeffs.iter().rev().fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+/// Evaluate a polynomial committed to in G1 at `x`.
+pub(crate) fn evaluate_commitment(coeffs: &[G1Projective], x: Scalar) -> G1Projective {
+    coeffs.iter().rev().fold(G1Projective::identity(), |acc, c| acc * x + c)
+}
+
+/// The Lagrange coefficients for interpolating at zero from the given nodes.
+///
+/// The indices must be distinct.
+pub(crate) fn lagrange_at_zero(indices: &[usize]) -> Vec<Scalar> {
+    indices
+        .iter()
+        .map(|&i| {
+            let xi = point(i);
+            let (num, den) = indices.iter().filter(|&&j| j != i).fold(
+                (Scalar::ONE, Scalar::ONE),
+                |(num, den), &j| {
+                    let xj = point(j);
+                    (num * xj, den * (xj - xi))
+                },
+            );
+            // The denominator is non-zero since the indices are distinct.
+            num * den.invert().unwrap()
+        })
+        .collect()
+}
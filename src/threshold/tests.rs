@@ -0,0 +1,123 @@
+use super::*;
+use crate::{Error, ThresholdError};
+
+fn sign_all(shares: &[SecretKeyShare], msg: &[u8]) -> Vec<SignatureShare> {
+    shares.iter().map(|s| s.sign(msg).unwrap()).collect()
+}
+
+#[test]
+fn threshold_dealer_sign_combine() {
+    let (pks, shares) = deal(3, 5).unwrap();
+    assert_eq!(pks.threshold(), 3);
+    let msg = "block 42".as_bytes();
+    let sig_shares = sign_all(&shares, msg);
+    for (share, sig) in shares.iter().zip(&sig_shares) {
+        assert_eq!(pks.verification_key(share.index()), share.public());
+        assert!(pks.verify_share(msg, sig));
+        assert!(!pks.verify_share("block 43".as_bytes(), sig));
+    }
+
+    // Any subset of size `threshold` yields the same signature.
+    let sig = pks.combine(&sig_shares[..3]).unwrap();
+    assert_eq!(sig, pks.combine(&sig_shares[2..]).unwrap());
+    let shuffled = [sig_shares[4].clone(), sig_shares[0].clone(), sig_shares[2].clone()];
+    assert_eq!(sig, pks.combine(&shuffled).unwrap());
+    assert!(pks.group_key().verify(msg, &sig));
+    assert!(pks.public_key().verify(msg, &sig));
+    assert!(!pks.public_key().verify("block 43".as_bytes(), &sig));
+}
+
+#[test]
+fn threshold_not_enough_shares() {
+    let (pks, shares) = deal(3, 4).unwrap();
+    let sig_shares = sign_all(&shares, b"msg");
+    assert!(matches!(
+        pks.combine(&sig_shares[..2]),
+        Err(Error::Threshold(ThresholdError::NotEnoughShares { threshold: 3, got: 2 }))
+    ));
+    let duplicated = [sig_shares[0].clone(), sig_shares[0].clone(), sig_shares[1].clone()];
+    assert!(pks.combine(&duplicated).is_err());
+    assert!(matches!(
+        deal(0, 4),
+        Err(Error::Threshold(ThresholdError::InvalidThreshold { threshold: 0, nodes: 4 }))
+    ));
+    assert!(deal(5, 4).is_err());
+}
+
+#[test]
+fn threshold_bad_share() {
+    let (pks, shares) = deal(2, 3).unwrap();
+    let mut sig_shares = sign_all(&shares, b"msg");
+    sig_shares[1] = shares[1].sign(b"other msg").unwrap();
+    assert!(!pks.verify_share(b"msg", &sig_shares[1]));
+    let sig = pks.combine(&sig_shares[..2]).unwrap();
+    assert!(!pks.group_key().verify(b"msg", &sig));
+}
+
+#[test]
+fn threshold_codec() {
+    let (pks, shares) = deal(2, 3).unwrap();
+    let decoded: PublicKeySet = bincode::deserialize(&bincode::serialize(&pks).unwrap()).unwrap();
+    assert_eq!(decoded, pks);
+    let decoded: SecretKeyShare =
+        bincode::deserialize(&bincode::serialize(&shares[0]).unwrap()).unwrap();
+    assert_eq!(decoded, shares[0]);
+    let empty: (usize, Vec<crate::bls::PublicKey>) = (3, Vec::new());
+    assert!(bincode::deserialize::<PublicKeySet>(&bincode::serialize(&empty).unwrap()).is_err());
+    let too_few_nodes = (1usize, Vec::from(pks.clone()));
+    let encoded = bincode::serialize(&too_few_nodes).unwrap();
+    assert!(bincode::deserialize::<PublicKeySet>(&encoded).is_err());
+}
+
+#[test]
+fn threshold_out_of_range_index() {
+    let (pks, shares) = deal(2, 3).unwrap();
+    assert_eq!(pks.nodes(), 3);
+    let mut sig_shares = sign_all(&shares, b"msg");
+    for index in [3, usize::MAX] {
+        // A share of the group secret key, claiming an index outside the nodes.
+        let group_share = SecretKeyShare::new(index, crate::bls::SecretKey::from_seed(&[1; 32]));
+        let forged = group_share.sign(b"msg").unwrap();
+        assert!(!pks.verify_share(b"msg", &forged));
+        sig_shares[0] = forged;
+        assert!(matches!(
+            pks.combine(&sig_shares),
+            Err(Error::Threshold(ThresholdError::InvalidIndex { nodes: 3, .. }))
+        ));
+    }
+    // Index `usize::MAX` is a regular point, not zero.
+    assert_ne!(&pks.verification_key(usize::MAX), pks.group_key());
+}
+
+#[test]
+fn threshold_dkg() {
+    let (t, n) = (3, 4);
+    let mut nodes: Vec<dkg::Participant> =
+        (0..n).map(|i| dkg::Participant::new(i, t, n).unwrap()).collect();
+    let commitments: Vec<dkg::Commitment> = nodes.iter().map(|p| p.commitment()).collect();
+    let shares: Vec<Vec<dkg::Share>> =
+        nodes.iter().map(|p| (0..n).map(|j| p.share_for(j)).collect()).collect();
+    for (j, node) in nodes.iter_mut().enumerate() {
+        for (i, commitment) in commitments.iter().enumerate() {
+            node.receive(commitment, &shares[i][j]).unwrap();
+        }
+        // A share checked against the wrong commitment is rejected.
+        assert!(matches!(
+            node.receive(&commitments[(j + 1) % n], &shares[j][j]),
+            Err(Error::Threshold(ThresholdError::InvalidShare { .. }))
+        ));
+    }
+
+    let dealers = nodes[0].dealers();
+    assert_eq!(dealers, vec![0, 1, 2, 3]);
+    let results: Vec<_> = nodes.into_iter().map(|p| p.finish(&dealers).unwrap()).collect();
+    let pks = results[0].0.clone();
+    assert!(results.iter().all(|(p, _)| *p == pks));
+
+    let msg = "epoch 7".as_bytes();
+    let sig_shares: Vec<SignatureShare> =
+        results.iter().map(|(_, s)| s.sign(msg).unwrap()).collect();
+    assert!(sig_shares.iter().all(|s| pks.verify_share(msg, s)));
+    let sig = pks.combine(&sig_shares[1..]).unwrap();
+    assert!(pks.public_key().verify(msg, &sig));
+}
@@ -0,0 +1,116 @@
+//! Single-shot HPKE in base mode, as specified by [RFC 9180], with the
+//! DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20-Poly1305 suite.
+//!
+//! The ciphertext is the encapsulated ephemeral public key followed by the
+//! sealed message, and the HPKE `info` is empty.
+//!
+//! [RFC 9180]: https://www.rfc-editor.org/rfc/rfc9180.html
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use ring::{hkdf, hmac};
+use zeroize::Zeroizing;
+
+use super::session::OkmLen;
+use super::{PublicKey, SecretKey};
+use crate::Error;
+
+/// The suite ID of DHKEM(X25519, HKDF-SHA256).
+const KEM_SUITE_ID: &[u8] = b"KEM\x00\x20";
+/// The suite ID of DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20-Poly1305.
+const HPKE_SUITE_ID: &[u8] = b"HPKE\x00\x20\x00\x01\x00\x03";
+const MODE_BASE: u8 = 0;
+
+impl PublicKey {
+    /// Encrypt `msg` to the holder of the secret key with HPKE, authenticating
+    /// `aad` along with it.
+    ///
+    /// Fails if the public key has low order.
+    pub fn encrypt(&self, msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        seal(self, &SecretKey::generate(), &[], msg, aad)
+    }
+}
+
+impl SecretKey {
+    /// Decrypt a ciphertext of [`PublicKey::encrypt`] with the same `aad`.
+    pub fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        open(self, &[], ciphertext, aad)
+    }
+}
+
+/// Seal `msg` to `pk_r` with the ephemeral key `sk_e`.
+pub(super) fn seal(
+    pk_r: &PublicKey,
+    sk_e: &SecretKey,
+    info: &[u8],
+    msg: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let enc = sk_e.public();
+    let dh = sk_e.diffie_hellman(pk_r)?;
+    let (key, nonce) = key_schedule(&shared_secret(dh.as_bytes(), &enc, pk_r), info);
+    let ciphertext = ChaCha20Poly1305::new(&(*key).into())
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+        .expect("ChaCha20-Poly1305 encryption failed.");
+    Ok([&enc.encode()[..], &ciphertext].concat())
+}
+
+pub(super) fn open(
+    sk_r: &SecretKey,
+    info: &[u8],
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if ciphertext.len() < 32 {
+        return Err(Error::Decryption("ciphertext too short"));
+    }
+    let (enc, ciphertext) = ciphertext.split_at(32);
+    let enc = PublicKey::decode(enc.try_into().expect("32 bytes"));
+    let dh = sk_r.diffie_hellman(&enc)?;
+    let (key, nonce) = key_schedule(&shared_secret(dh.as_bytes(), &enc, &sk_r.public()), info);
+    ChaCha20Poly1305::new(&(*key).into())
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| Error::Decryption("authentication failed"))
+}
+
+/// The `ExtractAndExpand` step of DHKEM.
+fn shared_secret(dh: &[u8], enc: &PublicKey, pk_r: &PublicKey) -> Zeroizing<[u8; 32]> {
+    let prk = labeled_extract(KEM_SUITE_ID, &[], b"eae_prk", dh);
+    let mut shared_secret = Zeroizing::new([0; 32]);
+    let kem_context = [&enc.encode()[..], &pk_r.encode()].concat();
+    labeled_expand(KEM_SUITE_ID, &prk, b"shared_secret", &kem_context, &mut *shared_secret);
+    shared_secret
+}
+
+/// The key and base nonce of the base mode key schedule.
+fn key_schedule(shared_secret: &[u8; 32], info: &[u8]) -> (Zeroizing<[u8; 32]>, [u8; 12]) {
+    let psk_id_hash = labeled_extract(HPKE_SUITE_ID, &[], b"psk_id_hash", &[]);
+    let info_hash = labeled_extract(HPKE_SUITE_ID, &[], b"info_hash", info);
+    let context = [&[MODE_BASE][..], &*psk_id_hash, &*info_hash].concat();
+    let secret = labeled_extract(HPKE_SUITE_ID, shared_secret, b"secret", &[]);
+    let (mut key, mut nonce) = (Zeroizing::new([0; 32]), [0; 12]);
+    labeled_expand(HPKE_SUITE_ID, &secret, b"key", &context, &mut *key);
+    labeled_expand(HPKE_SUITE_ID, &secret, b"base_nonce", &context, &mut nonce);
+    (key, nonce)
+}
+
+fn labeled_extract(suite_id: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]) -> Zeroizing<[u8; 32]> {
+    // HMAC pads keys with zeros, so an empty salt is the same as the
+    // `Nh` zero bytes of RFC 5869.
+    let mut ctx = hmac::Context::with_key(&hmac::Key::new(hmac::HMAC_SHA256, salt));
+    for part in [b"HPKE-v1", suite_id, label, ikm] {
+        ctx.update(part);
+    }
+    let mut prk = Zeroizing::new([0; 32]);
+    prk.copy_from_slice(ctx.sign().as_ref());
+    prk
+}
+
+fn labeled_expand(suite_id: &[u8], prk: &[u8; 32], label: &[u8], info: &[u8], out: &mut [u8]) {
+    let len = u16::try_from(out.len()).expect("short output").to_be_bytes();
+    let info = [&len[..], b"HPKE-v1", suite_id, label, info];
+    hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, prk)
+        .expand(&info, OkmLen(out.len()))
+        .and_then(|okm| okm.fill(out))
+        .expect("HKDF-SHA256 can expand short outputs");
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::ZeroizeOnDrop;
+
+use super::{PublicKey, SecretKey, SharedSecret};
+use crate::{ed25519, Error};
+
+/// A static X25519 keypair.
+///
+/// Keypairs are hashed and ordered by their public key.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Keypair {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+impl Keypair {
+    /// Generate a new X25519 keypair.
+    pub fn generate() -> Keypair { Keypair::from(SecretKey::generate()) }
+
+    /// Generate a new keypair from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Keypair {
+        Keypair::from(SecretKey::generate_with_rng(rng))
+    }
+
+    /// Deterministically derive a keypair from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> Keypair { Keypair::from(SecretKey::from_seed(seed)) }
+
+    /// Get the public key of this keypair.
+    pub fn public(&self) -> &PublicKey { &self.public }
+
+    /// Get the secret key of this keypair.
+    pub fn secret(&self) -> &SecretKey { &self.secret }
+
+    /// Perform a key exchange with `their_public`, see [`SecretKey::diffie_hellman`].
+    pub fn diffie_hellman(&self, their_public: &PublicKey) -> Result<SharedSecret, Error> {
+        self.secret.diffie_hellman(their_public)
+    }
+}
+
+impl ZeroizeOnDrop for Keypair {}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair").field("public", &self.public).finish()
+    }
+}
+
+/// Promote an X25519 secret key into a keypair.
+impl From<SecretKey> for Keypair {
+    fn from(secret: SecretKey) -> Keypair {
+        let public = secret.public();
+        Keypair { secret, public }
+    }
+}
+
+/// Demote an X25519 keypair into a secret key.
+impl From<Keypair> for SecretKey {
+    fn from(kp: Keypair) -> SecretKey { kp.secret }
+}
+
+/// Convert an Ed25519 keypair into an X25519 keypair, so that one identity
+/// key serves for both signatures and key exchange.
+impl From<&ed25519::Keypair> for Keypair {
+    fn from(kp: &ed25519::Keypair) -> Keypair {
+        let secret = SecretKey::from(&kp.secret());
+        Keypair { secret, public: PublicKey::from(&kp.public()) }
+    }
+}
+
+impl std::hash::Hash for Keypair {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.public.hash(state); }
+}
+
+impl PartialOrd for Keypair {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Keypair {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.public.cmp(&other.public) }
+}
@@ -0,0 +1,26 @@
+//! X25519 Diffie-Hellman key exchange.
+//!
+//! Ed25519 keys convert to X25519 keys through the birational map between the
+//! Edwards and Montgomery forms of Curve25519, so a node's identity key can
+//! also be used for key exchange. The shared secret of an exchange is turned
+//! into symmetric keys with [`SharedSecret::derive_session_keys`].
+//!
+//! Messages can be encrypted to a public key with [`PublicKey::encrypt`],
+//! which implements HPKE as specified by RFC 9180.
+
+mod keypair;
+pub use keypair::*;
+
+mod public;
+pub use public::*;
+
+mod secret;
+pub use secret::*;
+
+mod session;
+pub use session::*;
+
+mod hpke;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,47 @@
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ed25519;
+
+/// An X25519 public key.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PublicKey(pub(crate) x25519_dalek::PublicKey);
+
+impl PublicKey {
+    /// Encode the public key as its 32-byte Montgomery u-coordinate.
+    pub fn encode(&self) -> [u8; 32] { self.0.to_bytes() }
+
+    /// Decode a public key from its 32-byte Montgomery u-coordinate. Every
+    /// 32-byte string is accepted; low-order keys are rejected by the key
+    /// exchange instead.
+    pub fn decode(k: &[u8; 32]) -> PublicKey { PublicKey(x25519_dalek::PublicKey::from(*k)) }
+}
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PublicKey(u): ")?;
+        for byte in self.0.as_bytes() {
+            write!(f, "{:x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.as_bytes().cmp(other.0.as_bytes())
+    }
+}
+
+/// Convert an Ed25519 public key into the X25519 public key of the same
+/// secret key.
+impl From<&ed25519::PublicKey> for PublicKey {
+    fn from(pk: &ed25519::PublicKey) -> PublicKey {
+        PublicKey(x25519_dalek::PublicKey::from(pk.0.to_montgomery().to_bytes()))
+    }
+}
@@ -0,0 +1,117 @@
+use core::fmt;
+
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+use super::{PublicKey, SharedSecret};
+use crate::{ed25519, Error};
+
+/// A static X25519 secret key, which can be used for any number of key
+/// exchanges.
+///
+/// The key is wiped from memory when dropped.
+///
+/// Secret keys are compared in constant time, and are deliberately neither
+/// `Hash` nor `Ord`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SecretKey(pub(crate) x25519_dalek::StaticSecret);
+
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice { self.0.as_bytes().ct_eq(other.0.as_bytes()) }
+}
+
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool { self.ct_eq(other).into() }
+}
+
+impl Eq for SecretKey {}
+
+/// `x25519_dalek::StaticSecret` wipes itself on drop.
+impl ZeroizeOnDrop for SecretKey {}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SecretKey") }
+}
+
+impl SecretKey {
+    /// Generate a new X25519 secret key.
+    pub fn generate() -> SecretKey { Self::generate_with_rng(&mut rand::thread_rng()) }
+
+    /// Generate a new X25519 secret key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SecretKey {
+        SecretKey(x25519_dalek::StaticSecret::random_from_rng(rng))
+    }
+
+    /// Deterministically derive a secret key from a 32-byte seed, see
+    /// [`crate::Keypair::from_seed`].
+    pub fn from_seed(seed: &[u8; 32]) -> SecretKey {
+        Self::generate_with_rng(&mut crate::seeded_rng(seed))
+    }
+
+    /// Get the public key of this secret key.
+    pub fn public(&self) -> PublicKey { PublicKey(x25519_dalek::PublicKey::from(&self.0)) }
+
+    /// Perform a key exchange with `their_public`.
+    ///
+    /// Fails if `their_public` has low order, which would make the shared
+    /// secret independent of this secret key.
+    pub fn diffie_hellman(&self, their_public: &PublicKey) -> Result<SharedSecret, Error> {
+        SharedSecret::new(self.0.diffie_hellman(&their_public.0))
+    }
+}
+
+/// Create a secret key from its 32 bytes. The bytes are clamped when the key
+/// is used, as specified by RFC 7748.
+impl From<[u8; 32]> for SecretKey {
+    fn from(bytes: [u8; 32]) -> SecretKey { SecretKey(x25519_dalek::StaticSecret::from(bytes)) }
+}
+
+/// Convert an Ed25519 secret key into an X25519 secret key, whose public key
+/// is the conversion of the Ed25519 public key.
+impl From<&ed25519::SecretKey> for SecretKey {
+    fn from(sk: &ed25519::SecretKey) -> SecretKey {
+        let scalar = Zeroizing::new(sk.signing_key.to_scalar_bytes());
+        SecretKey(x25519_dalek::StaticSecret::from(*scalar))
+    }
+}
+
+/// An ephemeral X25519 secret key, which is consumed by its only key exchange.
+///
+/// The key is wiped from memory when dropped.
+pub struct EphemeralSecret {
+    secret: x25519_dalek::EphemeralSecret,
+    public: PublicKey,
+}
+
+/// `x25519_dalek::EphemeralSecret` wipes itself on drop.
+impl ZeroizeOnDrop for EphemeralSecret {}
+
+impl fmt::Debug for EphemeralSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EphemeralSecret").field("public", &self.public).finish()
+    }
+}
+
+impl EphemeralSecret {
+    /// Generate a new ephemeral secret key.
+    pub fn generate() -> EphemeralSecret { Self::generate_with_rng(&mut rand::thread_rng()) }
+
+    /// Generate a new ephemeral secret key from `rng`.
+    pub fn generate_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> EphemeralSecret {
+        let secret = x25519_dalek::EphemeralSecret::random_from_rng(rng);
+        let public = PublicKey(x25519_dalek::PublicKey::from(&secret));
+        EphemeralSecret { secret, public }
+    }
+
+    /// Get the public key to send to the peer.
+    pub fn public(&self) -> &PublicKey { &self.public }
+
+    /// Perform the key exchange with `their_public`, consuming the secret key.
+    ///
+    /// Fails if `their_public` has low order, see [`SecretKey::diffie_hellman`].
+    pub fn diffie_hellman(self, their_public: &PublicKey) -> Result<SharedSecret, Error> {
+        SharedSecret::new(self.secret.diffie_hellman(&their_public.0))
+    }
+}
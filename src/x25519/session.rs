@@ -0,0 +1,76 @@
+use core::fmt;
+
+use ring::hkdf;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::Error;
+
+/// The shared secret of an X25519 key exchange.
+///
+/// The secret is wiped from memory when dropped. It should not be used as a
+/// key directly, but through [`SharedSecret::derive_session_keys`].
+pub struct SharedSecret(x25519_dalek::SharedSecret);
+
+/// `x25519_dalek::SharedSecret` wipes itself on drop.
+impl ZeroizeOnDrop for SharedSecret {}
+
+impl fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SharedSecret") }
+}
+
+/// The HKDF info of session keys, followed by the transcript.
+const SESSION_KEYS_INFO: &[u8] = b"libcrypto x25519 session keys v1";
+
+impl SharedSecret {
+    pub(crate) fn new(shared: x25519_dalek::SharedSecret) -> Result<SharedSecret, Error> {
+        if !shared.was_contributory() {
+            return Err(Error::KeyExchange("low-order public key"));
+        }
+        Ok(SharedSecret(shared))
+    }
+
+    /// The raw 32-byte output of the key exchange.
+    pub fn as_bytes(&self) -> &[u8; 32] { self.0.as_bytes() }
+
+    /// Derive a pair of session keys from the shared secret with HKDF-SHA256.
+    ///
+    /// The `transcript` should contain everything both sides agreed on in the
+    /// handshake, e.g. the identity and ephemeral public keys of both peers,
+    /// so that the keys are bound to this exchange. Both sides must pass the
+    /// same transcript to obtain the same keys.
+    pub fn derive_session_keys(&self, transcript: &[u8]) -> SessionKeys {
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &[]).extract(self.as_bytes());
+        let info = [SESSION_KEYS_INFO, transcript];
+        let mut okm = [0; 64];
+        prk.expand(&info, OkmLen(okm.len()))
+            .and_then(|okm_ref| okm_ref.fill(&mut okm))
+            .expect("HKDF-SHA256 can expand 64 bytes");
+        let mut keys = SessionKeys { initiator: [0; 32], responder: [0; 32] };
+        keys.initiator.copy_from_slice(&okm[..32]);
+        keys.responder.copy_from_slice(&okm[32..]);
+        okm.zeroize();
+        keys
+    }
+}
+
+/// A pair of 256-bit session keys, one for each direction of a channel.
+///
+/// The keys are wiped from memory when dropped.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct SessionKeys {
+    /// The key of messages sent by the initiator of the exchange.
+    pub initiator: [u8; 32],
+    /// The key of messages sent by the responder of the exchange.
+    pub responder: [u8; 32],
+}
+
+impl fmt::Debug for SessionKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "SessionKeys") }
+}
+
+/// The length of HKDF output, as ring wants it.
+pub(super) struct OkmLen(pub(super) usize);
+
+impl hkdf::KeyType for OkmLen {
+    fn len(&self) -> usize { self.0 }
+}
@@ -0,0 +1,119 @@
+use super::*;
+use crate::ed25519;
+use crate::tests::hex;
+
+fn bytes32(s: &str) -> [u8; 32] { hex(s).try_into().unwrap() }
+
+#[test]
+fn x25519_rfc7748_vectors() {
+    // Section 6.1 of RFC 7748.
+    let alice = SecretKey::from(bytes32(
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+    ));
+    let bob = SecretKey::from(bytes32(
+        "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+    ));
+    assert_eq!(
+        alice.public().encode(),
+        bytes32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert_eq!(
+        bob.public().encode(),
+        bytes32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+    );
+    let shared = bytes32("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(alice.diffie_hellman(&bob.public()).unwrap().as_bytes(), &shared);
+    assert_eq!(bob.diffie_hellman(&alice.public()).unwrap().as_bytes(), &shared);
+}
+
+#[test]
+fn x25519_ephemeral_exchange() {
+    let responder = Keypair::generate();
+    let ephemeral = EphemeralSecret::generate();
+    let ephemeral_public = *ephemeral.public();
+    let initiator_shared = ephemeral.diffie_hellman(responder.public()).unwrap();
+    let responder_shared = responder.diffie_hellman(&ephemeral_public).unwrap();
+    assert_eq!(initiator_shared.as_bytes(), responder_shared.as_bytes());
+}
+
+#[test]
+fn x25519_rejects_low_order_keys() {
+    let kp = Keypair::generate();
+    let one = bytes32("0100000000000000000000000000000000000000000000000000000000000000");
+    for u in [[0; 32], one] {
+        let err = kp.diffie_hellman(&PublicKey::decode(&u)).unwrap_err();
+        assert!(matches!(err, crate::Error::KeyExchange(_)));
+    }
+}
+
+#[test]
+fn x25519_from_ed25519() {
+    let ed = ed25519::Keypair::generate().unwrap();
+    let kp = Keypair::from(&ed);
+    assert_eq!(kp.public(), &kp.secret().public());
+    assert_eq!(kp.public(), &PublicKey::from(&ed.public()));
+
+    let peer = Keypair::generate();
+    let shared = peer.diffie_hellman(&PublicKey::from(&ed.public())).unwrap();
+    assert_eq!(kp.diffie_hellman(peer.public()).unwrap().as_bytes(), shared.as_bytes());
+}
+
+#[test]
+fn x25519_session_keys() {
+    let (a, b) = (Keypair::generate(), Keypair::generate());
+    let transcript = [a.public().encode(), b.public().encode()].concat();
+    let keys_a = a.diffie_hellman(b.public()).unwrap().derive_session_keys(&transcript);
+    let keys_b = b.diffie_hellman(a.public()).unwrap().derive_session_keys(&transcript);
+    assert_eq!((keys_a.initiator, keys_a.responder), (keys_b.initiator, keys_b.responder));
+    assert_ne!(keys_a.initiator, keys_a.responder);
+
+    let other = a.diffie_hellman(b.public()).unwrap().derive_session_keys(b"another transcript");
+    assert_ne!(other.initiator, keys_a.initiator);
+}
+
+#[test]
+fn x25519_keypair_serde() {
+    let kp = Keypair::from_seed(&[3; 32]);
+    let decoded: Keypair = bincode::deserialize(&bincode::serialize(&kp).unwrap()).unwrap();
+    assert_eq!(decoded, kp);
+    assert_eq!(Keypair::from_seed(&[3; 32]), kp);
+}
+
+#[test]
+fn hpke_rfc9180_vector() {
+    // Appendix A.2.1 of RFC 9180, DHKEM(X25519, HKDF-SHA256), HKDF-SHA256,
+    // ChaCha20Poly1305 in base mode, first encryption.
+    let sk_e = SecretKey::from(bytes32(
+        "f4ec9b33b792c372c1d2c2063507b684ef925b8c75a42dbcbf57d63ccd381600",
+    ));
+    let sk_r = SecretKey::from(bytes32(
+        "8057991eef8f1f1af18f4a9491d16a1ce333f695d4db8e38da75975c4478e0fb",
+    ));
+    let info = hex("4f6465206f6e2061204772656369616e2055726e");
+    let pt = hex("4265617574792069732074727574682c20747275746820626561757479");
+    let aad = hex("436f756e742d30");
+    let expected = hex(concat!(
+        "1afa08d3dec047a643885163f1180476fa7ddb54c6a8029ea33f95796bf2ac4a",
+        "1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db",
+        "21993c62ce81883d2dd1b51a28",
+    ));
+    let ct = hpke::seal(&sk_r.public(), &sk_e, &info, &pt, &aad).unwrap();
+    assert_eq!(ct, expected);
+    assert_eq!(hpke::open(&sk_r, &info, &ct, &aad).unwrap(), pt);
+}
+
+#[test]
+fn hpke_encrypt_decrypt() {
+    let kp = Keypair::generate();
+    let ct = kp.public().encrypt(b"share", b"epoch 1").unwrap();
+    assert_eq!(kp.secret().decrypt(&ct, b"epoch 1").unwrap(), b"share");
+    assert_ne!(kp.public().encrypt(b"share", b"epoch 1").unwrap(), ct);
+
+    assert!(kp.secret().decrypt(&ct, b"epoch 2").is_err());
+    assert!(Keypair::generate().secret().decrypt(&ct, b"epoch 1").is_err());
+    assert!(kp.secret().decrypt(&ct[..31], b"epoch 1").is_err());
+    let mut tampered = ct.clone();
+    tampered[40] ^= 1;
+    assert!(kp.secret().decrypt(&tampered, b"epoch 1").is_err());
+    assert!(PublicKey::decode(&[0; 32]).encrypt(b"share", b"").is_err());
+}